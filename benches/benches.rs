@@ -3,12 +3,24 @@
 use std::hint::black_box;
 
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
-use netoptim_rs::dijkstra::{dijkstra, dijkstra_path};
+use netoptim_rs::dijkstra::{
+    astar, bidirectional_dijkstra, dijkstra, dijkstra_path, dijkstra_with_queue,
+};
+use netoptim_rs::monotone_queue::{BucketQueue, RadixHeap};
 use netoptim_rs::neg_cycle::NegCycleFinder;
+use netoptim_rs::parametric::{MaxParametricSolver, ParametricAPI};
+use netoptim_rs::{bellman_ford, goldberg_radzik, spfa};
 use num::rational::Ratio;
 use petgraph::graph::{DiGraph, Graph};
 use petgraph::prelude::*;
 
+// Random graph fixtures, shared with the unit tests.
+#[allow(dead_code)]
+#[path = "../src/test_utils.rs"]
+mod test_utils;
+
+use test_utils::{grid_graph, Lcg};
+
 fn create_dense_graph(num_nodes: usize) -> Graph<(), f64> {
     let mut graph = Graph::new();
     let nodes: Vec<NodeIndex> = (0..num_nodes).map(|_| graph.add_node(())).collect();
@@ -40,6 +52,37 @@ fn create_sparse_graph(num_nodes: usize, avg_degree: usize) -> Graph<(), f64> {
     graph
 }
 
+/// A `side` x `side` grid with two-way streets of pseudo-random length, as a stand-in
+/// for a sparse road network.
+fn create_road_graph(side: usize) -> Graph<(), f64> {
+    grid_graph(&mut Lcg::new(12345), side, |rng| {
+        1.0 + rng.below(100) as f64
+    })
+}
+
+/// A layered DAG with negative arcs whose node indices run against the topological
+/// order, so that each Bellman-Ford pass only moves the distances one layer further.
+fn create_layered_dag(layers: usize, width: usize) -> Graph<(), i64> {
+    let mut graph = Graph::new();
+    let nodes: Vec<NodeIndex> = (0..layers * width).map(|_| graph.add_node(())).collect();
+    let node = |layer: usize, k: usize| nodes[(layers - 1 - layer) * width + k];
+
+    for layer in 0..layers - 1 {
+        for k in 0..width {
+            for step in 0..3 {
+                let j = (k + step) % width;
+                let weight = (k * 7 + j * 3 + layer) as i64 % 11 - 5;
+                graph.add_edge(node(layer, k), node(layer + 1, j), weight);
+            }
+        }
+    }
+    for k in 1..width {
+        graph.add_edge(node(0, 0), node(0, k), 0);
+    }
+
+    graph
+}
+
 fn create_graph_with_negative_cycle(num_nodes: usize) -> DiGraph<(), Ratio<i32>> {
     let mut graph = DiGraph::new();
     let nodes: Vec<NodeIndex> = (0..num_nodes).map(|_| graph.add_node(())).collect();
@@ -125,6 +168,79 @@ fn bench_neg_cycle_finder(c: &mut Criterion) {
     group.finish();
 }
 
+fn create_timing_graph(num_nodes: usize) -> DiGraph<(), f64> {
+    let mut graph = DiGraph::new();
+    let nodes: Vec<NodeIndex> = (0..num_nodes).map(|_| graph.add_node(())).collect();
+
+    // Backward chain of negative arcs: each relaxation pass only pushes the
+    // distances one step further, so `howard` runs many passes over the graph.
+    for i in 0..num_nodes - 1 {
+        graph.add_edge(nodes[i + 1], nodes[i], -1.0);
+    }
+    for i in 0..num_nodes {
+        graph.add_edge(nodes[i], nodes[(i + 7) % num_nodes], 10.0);
+        graph.add_edge(nodes[i], nodes[(i * 13 + 5) % num_nodes], 10.0);
+    }
+    graph.add_edge(nodes[0], nodes[num_nodes - 1], num_nodes as f64);
+
+    graph
+}
+
+fn bench_neg_cycle_finder_workspace(c: &mut Criterion) {
+    let mut group = c.benchmark_group("neg_cycle_finder_workspace");
+    group.sample_size(10);
+
+    for size in [500, 1000, 2000].iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, &size| {
+            let graph = create_timing_graph(size);
+            let mut ncf = NegCycleFinder::new(&graph);
+            let mut dist = vec![0.0; size];
+
+            b.iter(|| {
+                dist.iter_mut().for_each(|d| *d = 0.0);
+                black_box(ncf.howard(black_box(&mut dist), |e| *e.weight()))
+            });
+        });
+    }
+
+    group.finish();
+}
+
+struct MeanCycleAPI;
+
+impl<E: EdgeRef<Weight = f64>> ParametricAPI<E, f64> for MeanCycleAPI {
+    fn distance(&self, ratio: &f64, edge: &E) -> f64 {
+        *edge.weight() - *ratio
+    }
+
+    fn zero_cancel(&self, cycle: &[E]) -> f64 {
+        let total: f64 = cycle.iter().map(|e| *e.weight()).sum();
+        total / cycle.len() as f64
+    }
+}
+
+fn bench_max_parametric_solver(c: &mut Criterion) {
+    let mut group = c.benchmark_group("max_parametric_solver");
+    group.sample_size(10);
+
+    for size in [500, 1000, 2000].iter() {
+        group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, &size| {
+            let graph = create_timing_graph(size);
+            let mut solver = MaxParametricSolver::new(&graph, MeanCycleAPI);
+            let mut dist = vec![0.0; size];
+
+            b.iter(|| {
+                dist.iter_mut().for_each(|d| *d = 0.0);
+                let mut ratio = 10.0;
+                black_box(solver.run(black_box(&mut dist), &mut ratio));
+                black_box(ratio)
+            });
+        });
+    }
+
+    group.finish();
+}
+
 fn bench_graph_creation(c: &mut Criterion) {
     let mut group = c.benchmark_group("graph_creation");
 
@@ -177,14 +293,156 @@ fn bench_comparison_dijkstra_vs_bellman_ford(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_spfa_vs_bellman_ford(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spfa_vs_bellman_ford");
+
+    for side in [10, 30, 60].iter() {
+        let graph = create_road_graph(*side);
+        let source = NodeIndex::new(0);
+
+        group.bench_with_input(BenchmarkId::new("bellman_ford", side), side, |b, _| {
+            b.iter(|| black_box(bellman_ford(black_box(&graph), black_box(source))));
+        });
+
+        group.bench_with_input(BenchmarkId::new("spfa", side), side, |b, _| {
+            b.iter(|| black_box(spfa(black_box(&graph), black_box(source))));
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_goldberg_radzik(c: &mut Criterion) {
+    let mut group = c.benchmark_group("goldberg_radzik_layered_dag");
+
+    for layers in [20, 50, 100].iter() {
+        let graph = create_layered_dag(*layers, 20);
+        let source = NodeIndex::new((layers - 1) * 20);
+
+        group.bench_with_input(BenchmarkId::new("bellman_ford", layers), layers, |b, _| {
+            b.iter(|| black_box(bellman_ford(black_box(&graph), black_box(source))));
+        });
+
+        group.bench_with_input(BenchmarkId::new("spfa", layers), layers, |b, _| {
+            b.iter(|| black_box(spfa(black_box(&graph), black_box(source))));
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("goldberg_radzik", layers),
+            layers,
+            |b, _| {
+                b.iter(|| black_box(goldberg_radzik(black_box(&graph), black_box(source))));
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn bench_point_to_point(c: &mut Criterion) {
+    let mut group = c.benchmark_group("point_to_point");
+
+    for side in [30, 100, 300].iter() {
+        let graph = create_road_graph(*side);
+        let source = NodeIndex::new(0);
+        // The grid centre; every road is at least 1 long, so the Manhattan
+        // distance is a consistent heuristic.
+        let (tr, tc) = (side / 2, side / 2);
+        let target = NodeIndex::new(tr * side + tc);
+        let manhattan = |v: NodeIndex| {
+            let (r, c) = (v.index() / side, v.index() % side);
+            (r.abs_diff(tr) + c.abs_diff(tc)) as f64
+        };
+
+        group.bench_with_input(BenchmarkId::new("dijkstra_path", side), side, |b, _| {
+            b.iter(|| {
+                black_box(dijkstra_path(
+                    black_box(&graph),
+                    black_box(source),
+                    black_box(target),
+                ))
+            });
+        });
+
+        group.bench_with_input(
+            BenchmarkId::new("bidirectional_dijkstra", side),
+            side,
+            |b, _| {
+                b.iter(|| {
+                    black_box(bidirectional_dijkstra(
+                        black_box(&graph),
+                        black_box(source),
+                        black_box(target),
+                    ))
+                });
+            },
+        );
+
+        group.bench_with_input(BenchmarkId::new("astar", side), side, |b, _| {
+            b.iter(|| {
+                black_box(astar(
+                    black_box(&graph),
+                    black_box(source),
+                    black_box(target),
+                    manhattan,
+                ))
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_integer_queues(c: &mut Criterion) {
+    let mut group = c.benchmark_group("integer_queues");
+
+    for side in [30, 100, 300].iter() {
+        // The road grid with lengths 1..=100 as integers.
+        let graph = create_road_graph(*side).map(|_, _| (), |_, &w| w as u32);
+        let source = NodeIndex::new(0);
+
+        group.bench_with_input(BenchmarkId::new("binary_heap", side), side, |b, _| {
+            b.iter(|| black_box(dijkstra(black_box(&graph), black_box(source))));
+        });
+
+        group.bench_with_input(BenchmarkId::new("dial_buckets", side), side, |b, _| {
+            b.iter(|| {
+                black_box(dijkstra_with_queue(
+                    black_box(&graph),
+                    black_box(source),
+                    BucketQueue::new(),
+                ))
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("radix_heap", side), side, |b, _| {
+            b.iter(|| {
+                black_box(dijkstra_with_queue(
+                    black_box(&graph),
+                    black_box(source),
+                    RadixHeap::new(),
+                ))
+            });
+        });
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_dijkstra_sparse,
     bench_dijkstra_dense,
     bench_dijkstra_path,
     bench_neg_cycle_finder,
+    bench_neg_cycle_finder_workspace,
+    bench_max_parametric_solver,
     bench_graph_creation,
-    bench_comparison_dijkstra_vs_bellman_ford
+    bench_comparison_dijkstra_vs_bellman_ford,
+    bench_spfa_vs_bellman_ford,
+    bench_goldberg_radzik,
+    bench_point_to_point,
+    bench_integer_queues
 );
 
 criterion_main!(benches);
@@ -30,16 +30,19 @@ fn main() {
     let result = dijkstra(&g, a).unwrap();
     println!("Distances from node A:");
     for (i, dist) in result.distances.iter().enumerate() {
-        println!("  Node {}: {}", i, dist);
+        match dist {
+            Some(dist) => println!("  Node {}: {}", i, dist),
+            None => println!("  Node {}: unreachable", i),
+        }
     }
 
     println!("\nShortest path from A to E:");
-    if let Some(path) = dijkstra_path(&g, a, e) {
+    if let Ok(path) = dijkstra_path(&g, a, e) {
         println!(
             "  Path: {:?}",
             path.iter().map(|n| g[*n]).collect::<Vec<_>>()
         );
-        println!("  Cost: {}", result.distances[e.index()]);
+        println!("  Cost: {}", result.distances[e.index()].unwrap());
     }
 
     // Example 2: Network routing
@@ -68,12 +71,15 @@ fn main() {
     let result = dijkstra(&network, source).unwrap();
     println!("Routing from router 0 to router 5:");
 
-    if let Some(path) = dijkstra_path(&network, source, destination) {
+    if let Ok(path) = dijkstra_path(&network, source, destination) {
         println!(
             "  Optimal path: {:?}",
             path.iter().map(|n| n.index()).collect::<Vec<_>>()
         );
-        println!("  Total cost: {}", result.distances[destination.index()]);
+        println!(
+            "  Total cost: {}",
+            result.distances[destination.index()].unwrap()
+        );
 
         println!("\n  Detailed route:");
         for i in 0..path.len() - 1 {
@@ -114,11 +120,9 @@ fn main() {
         print!("{:2}: ", i);
         let result = dijkstra(&g, *node).unwrap();
         for j in 0..4 {
-            let dist: f64 = result.distances[j];
-            if dist.is_infinite() {
-                print!("  INF");
-            } else {
-                print!("{:5.1}", dist);
+            match result.distances[j] {
+                Some(dist) => print!("{:5.1}", dist),
+                None => print!("  INF"),
             }
         }
         println!();
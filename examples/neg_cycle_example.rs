@@ -4,8 +4,7 @@ use netoptim_rs::neg_cycle::NegCycleFinder;
 use netoptim_rs::parametric::{MaxParametricSolver, ParametricAPI};
 use num::rational::Ratio;
 use petgraph::graph::DiGraph;
-use petgraph::graph::EdgeReference;
-use petgraph::visit::EdgeRef as _;
+use petgraph::visit::EdgeRef;
 
 fn main() {
     println!("=== Negative Cycle Detection Examples ===\n");
@@ -101,12 +100,12 @@ fn main() {
 
     struct TestParametricAPI;
 
-    impl ParametricAPI<(), Ratio<i32>> for TestParametricAPI {
-        fn distance(&self, ratio: &Ratio<i32>, edge: &EdgeReference<Ratio<i32>>) -> Ratio<i32> {
+    impl<E: EdgeRef<Weight = Ratio<i32>>> ParametricAPI<E, Ratio<i32>> for TestParametricAPI {
+        fn distance(&self, ratio: &Ratio<i32>, edge: &E) -> Ratio<i32> {
             *edge.weight() - *ratio
         }
 
-        fn zero_cancel(&self, cycle: &[EdgeReference<Ratio<i32>>]) -> Ratio<i32> {
+        fn zero_cancel(&self, cycle: &[E]) -> Ratio<i32> {
             let sum_a: Ratio<i32> = cycle.iter().map(|e| *e.weight()).sum();
             let sum_b = Ratio::new(cycle.len() as i32, 1);
             sum_a / sum_b
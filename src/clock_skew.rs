@@ -0,0 +1,354 @@
+//! Clock skew scheduling for synchronous circuits.
+//!
+//! Each combinational path from register `i` to register `j` with delays
+//! `[d_min, d_max]` yields two constraints on the clock arrival times `t`:
+//!
+//! * setup: `t_i + d_max + T_setup <= t_j + T`, i.e. an arc `j -> i` of weight
+//!   `T - d_max - T_setup`;
+//! * hold: `t_i + d_min >= t_j + T_hold`, i.e. an arc `i -> j` of weight
+//!   `d_min - T_hold`.
+//!
+//! A skew schedule exists iff this sequential timing graph has no negative cycle.
+//! [`ClockSkewScheduler`](crate::clock_skew::ClockSkewScheduler) builds the graph and
+//! uses the parametric solvers to find either the maximal safety margin for a given
+//! clock period ([`MaxParametricSolver`](crate::parametric::MaxParametricSolver)) or the
+//! minimal clock period ([`MinParametricSolver`](crate::parametric::MinParametricSolver)),
+//! together with the optimal skew of every register and the critical cycle.
+
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::{EdgeFiltered, EdgeRef};
+
+use crate::error::NetOptimError;
+use crate::neg_cycle::NegCycleFinder;
+use crate::parametric::{MaxParametricSolver, MinParametricSolver, ParametricAPI};
+
+/// The kind of timing constraint an arc of the timing graph represents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimingConstraint {
+    /// Long-path (setup) constraint, pointing from the capturing to the launching register.
+    Setup,
+    /// Short-path (hold) constraint, pointing from the launching to the capturing register.
+    Hold,
+}
+
+/// An arc of the sequential timing graph.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimingArc {
+    /// The register the arc leaves.
+    pub from: usize,
+    /// The register the arc enters.
+    pub to: usize,
+    /// The constraint the arc models.
+    pub kind: TimingConstraint,
+    /// The path delay (`d_max` for setup, `d_min` for hold) plus setup or minus hold time.
+    pub delay: f64,
+}
+
+/// Result of a clock skew optimization.
+#[derive(Debug, Clone)]
+pub struct SkewSchedule {
+    /// The optimal safety margin or clock period.
+    pub value: f64,
+    /// The clock arrival time (skew) of every register.
+    pub skews: Vec<f64>,
+    /// The cycle of constraints limiting `value`, in forward order.
+    pub critical_cycle: Vec<TimingArc>,
+    /// Whether the constraints can be met (non-negative margin, or finite period).
+    pub feasible: bool,
+}
+
+/// Clock skew scheduler.
+///
+/// # Example
+/// ```rust
+/// use netoptim_rs::clock_skew::ClockSkewScheduler;
+///
+/// let mut scheduler = ClockSkewScheduler::new(3);
+/// scheduler.add_path(0, 1, 1.0, 4.0);
+/// scheduler.add_path(1, 2, 1.0, 2.0);
+/// scheduler.add_path(2, 0, 1.0, 3.0);
+///
+/// // The loop needs 9 time units over 3 stages.
+/// let schedule = scheduler.min_clock_period().unwrap();
+/// assert!((schedule.value - 3.0).abs() < 1e-9);
+///
+/// let schedule = scheduler.max_safety_margin(4.0).unwrap();
+/// assert!(schedule.feasible);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClockSkewScheduler {
+    num_registers: usize,
+    paths: Vec<(usize, usize, f64, f64)>,
+    setup_time: f64,
+    hold_time: f64,
+}
+
+impl ClockSkewScheduler {
+    /// Creates a new [`ClockSkewScheduler`] for `num_registers` registers with zero
+    /// setup and hold times.
+    pub fn new(num_registers: usize) -> Self {
+        Self {
+            num_registers,
+            paths: Vec::new(),
+            setup_time: 0.0,
+            hold_time: 0.0,
+        }
+    }
+
+    /// Sets the setup and hold times applied to every register.
+    pub fn with_setup_hold(mut self, setup_time: f64, hold_time: f64) -> Self {
+        self.setup_time = setup_time;
+        self.hold_time = hold_time;
+        self
+    }
+
+    /// Adds a combinational path from register `from` to register `to` with the given
+    /// minimum and maximum delays.
+    pub fn add_path(&mut self, from: usize, to: usize, min_delay: f64, max_delay: f64) {
+        self.paths.push((from, to, min_delay, max_delay));
+    }
+
+    /// Builds the sequential timing graph; arc weights are evaluated later by the
+    /// parametric APIs.
+    fn timing_graph(&self) -> Result<DiGraph<(), TimingArc>, NetOptimError> {
+        let mut digraph = DiGraph::with_capacity(self.num_registers, 2 * self.paths.len());
+        for _ in 0..self.num_registers {
+            digraph.add_node(());
+        }
+        for &(from, to, min_delay, max_delay) in &self.paths {
+            if let Some(register) = [from, to].into_iter().find(|&r| r >= self.num_registers) {
+                return Err(NetOptimError::InvalidNode(register));
+            }
+            digraph.add_edge(
+                NodeIndex::new(to),
+                NodeIndex::new(from),
+                TimingArc {
+                    from: to,
+                    to: from,
+                    kind: TimingConstraint::Setup,
+                    delay: max_delay + self.setup_time,
+                },
+            );
+            digraph.add_edge(
+                NodeIndex::new(from),
+                NodeIndex::new(to),
+                TimingArc {
+                    from,
+                    to,
+                    kind: TimingConstraint::Hold,
+                    delay: min_delay - self.hold_time,
+                },
+            );
+        }
+        Ok(digraph)
+    }
+
+    /// Maximizes the safety margin `m` subtracted from every setup and hold slack for a
+    /// fixed clock `period`.
+    ///
+    /// The schedule is feasible iff the optimal margin is non-negative; the critical
+    /// cycle is the one attaining the margin.
+    pub fn max_safety_margin(&self, period: f64) -> Result<SkewSchedule, NetOptimError> {
+        let digraph = self.timing_graph()?;
+        if digraph.edge_count() == 0 {
+            return Ok(SkewSchedule {
+                value: f64::INFINITY,
+                skews: vec![0.0; self.num_registers],
+                critical_cycle: Vec::new(),
+                feasible: true,
+            });
+        }
+
+        let omega = MarginAPI { period };
+        // Every cycle mean is below the largest arc weight plus one, so the solver
+        // always reports the critical cycle.
+        let mut margin = digraph
+            .edge_references()
+            .map(|e| omega.slack(e.weight()))
+            .fold(f64::NEG_INFINITY, f64::max)
+            + 1.0;
+        let mut skews = vec![0.0; self.num_registers];
+        let mut solver = MaxParametricSolver::new(&digraph, omega);
+        let cycle = solver.run(&mut skews, &mut margin);
+        Ok(SkewSchedule {
+            value: margin,
+            skews,
+            critical_cycle: forward_cycle(&cycle),
+            feasible: margin >= 0.0,
+        })
+    }
+
+    /// Minimizes the clock period with zero safety margin.
+    ///
+    /// If the hold constraints alone form a negative cycle, no period can satisfy them;
+    /// the schedule is then infeasible with an infinite period and that cycle as the
+    /// critical cycle.
+    pub fn min_clock_period(&self) -> Result<SkewSchedule, NetOptimError> {
+        let digraph = self.timing_graph()?;
+
+        let hold_only =
+            EdgeFiltered::from_fn(&digraph, |e| e.weight().kind == TimingConstraint::Hold);
+        let mut ncf = NegCycleFinder::new(&hold_only);
+        let mut dist = vec![0.0; self.num_registers];
+        if let Some(cycle) = ncf.howard(&mut dist, |e| e.weight().delay) {
+            return Ok(SkewSchedule {
+                value: f64::INFINITY,
+                skews: dist,
+                critical_cycle: forward_cycle(&cycle),
+                feasible: false,
+            });
+        }
+
+        let mut period = 0.0;
+        let mut skews = vec![0.0; self.num_registers];
+        let mut solver = MinParametricSolver::new(&digraph, PeriodAPI);
+        let cycle = solver.run(&mut skews, &mut period);
+        Ok(SkewSchedule {
+            value: period,
+            skews,
+            critical_cycle: forward_cycle(&cycle),
+            feasible: true,
+        })
+    }
+}
+
+/// Puts the arcs of a cycle reported by a parametric solver into forward order.
+fn forward_cycle<E: EdgeRef<Weight = TimingArc>>(cycle: &[E]) -> Vec<TimingArc> {
+    let mut arcs: Vec<TimingArc> = cycle.iter().map(|e| *e.weight()).collect();
+    if arcs.len() > 1 && arcs[0].to != arcs[1].from {
+        arcs.reverse();
+    }
+    arcs
+}
+
+/// Safety margin problem: the distance of an arc is its slack minus the margin.
+struct MarginAPI {
+    period: f64,
+}
+
+impl MarginAPI {
+    fn slack(&self, arc: &TimingArc) -> f64 {
+        match arc.kind {
+            TimingConstraint::Setup => self.period - arc.delay,
+            TimingConstraint::Hold => arc.delay,
+        }
+    }
+}
+
+impl<E: EdgeRef<Weight = TimingArc>> ParametricAPI<E, f64> for MarginAPI {
+    fn distance(&self, ratio: &f64, edge: &E) -> f64 {
+        self.slack(edge.weight()) - *ratio
+    }
+
+    fn zero_cancel(&self, cycle: &[E]) -> f64 {
+        let total: f64 = cycle.iter().map(|e| self.slack(e.weight())).sum();
+        total / cycle.len() as f64
+    }
+}
+
+/// Clock period problem: setup arcs gain the period, hold arcs are fixed.
+struct PeriodAPI;
+
+impl<E: EdgeRef<Weight = TimingArc>> ParametricAPI<E, f64> for PeriodAPI {
+    fn distance(&self, ratio: &f64, edge: &E) -> f64 {
+        let arc = edge.weight();
+        match arc.kind {
+            TimingConstraint::Setup => *ratio - arc.delay,
+            TimingConstraint::Hold => arc.delay,
+        }
+    }
+
+    fn zero_cancel(&self, cycle: &[E]) -> f64 {
+        let mut total = 0.0;
+        let mut count = 0.0;
+        for edge in cycle {
+            let arc = edge.weight();
+            match arc.kind {
+                TimingConstraint::Setup => {
+                    total += arc.delay;
+                    count += 1.0;
+                }
+                TimingConstraint::Hold => total -= arc.delay,
+            }
+        }
+        total / count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_schedule_meets(scheduler: &ClockSkewScheduler, period: f64, skews: &[f64]) {
+        for &(i, j, min_delay, max_delay) in &scheduler.paths {
+            let setup_slack = skews[j] + period - (skews[i] + max_delay + scheduler.setup_time);
+            let hold_slack = skews[i] + min_delay - (skews[j] + scheduler.hold_time);
+            assert!(setup_slack >= -1e-9, "setup violated on {} -> {}", i, j);
+            assert!(hold_slack >= -1e-9, "hold violated on {} -> {}", i, j);
+        }
+    }
+
+    fn ring() -> ClockSkewScheduler {
+        let mut scheduler = ClockSkewScheduler::new(4);
+        scheduler.add_path(0, 1, 2.0, 5.0);
+        scheduler.add_path(1, 2, 1.0, 3.0);
+        scheduler.add_path(2, 3, 3.0, 6.0);
+        scheduler.add_path(3, 0, 2.0, 2.0);
+        scheduler.add_path(1, 3, 1.0, 4.0);
+        scheduler
+    }
+
+    #[test]
+    fn test_min_clock_period() {
+        let scheduler = ring();
+        let schedule = scheduler.min_clock_period().unwrap();
+        assert!(schedule.feasible);
+        // The ring 0 -> 1 -> 2 -> 3 -> 0 needs 16 units over 4 stages.
+        assert!((schedule.value - 4.0).abs() < 1e-9);
+        assert!(!schedule.critical_cycle.is_empty());
+        assert_schedule_meets(&scheduler, schedule.value, &schedule.skews);
+    }
+
+    #[test]
+    fn test_max_safety_margin() {
+        let scheduler = ring();
+        let schedule = scheduler.max_safety_margin(6.0).unwrap();
+        assert!(schedule.feasible);
+        assert!(schedule.value > 0.0);
+        assert!(!schedule.critical_cycle.is_empty());
+        assert_schedule_meets(&scheduler, 6.0, &schedule.skews);
+
+        let schedule = scheduler.max_safety_margin(3.0).unwrap();
+        assert!(!schedule.feasible);
+        let cycle = &schedule.critical_cycle;
+        for k in 0..cycle.len() {
+            assert_eq!(cycle[k].to, cycle[(k + 1) % cycle.len()].from);
+        }
+    }
+
+    #[test]
+    fn test_hold_violation_is_infeasible() {
+        // Launch on 0 reaches 1 too early for any skew once hold time is large.
+        let mut scheduler = ClockSkewScheduler::new(2).with_setup_hold(0.0, 2.0);
+        scheduler.add_path(0, 1, 1.0, 1.0);
+        scheduler.add_path(1, 0, 1.0, 1.0);
+        let schedule = scheduler.min_clock_period().unwrap();
+        assert!(!schedule.feasible);
+        assert!(schedule.value.is_infinite());
+        assert_eq!(schedule.critical_cycle.len(), 2);
+        assert!(schedule
+            .critical_cycle
+            .iter()
+            .all(|arc| arc.kind == TimingConstraint::Hold && arc.delay == -1.0));
+    }
+
+    #[test]
+    fn test_invalid_register() {
+        let mut scheduler = ClockSkewScheduler::new(2);
+        scheduler.add_path(0, 5, 1.0, 2.0);
+        assert_eq!(
+            scheduler.min_clock_period().unwrap_err(),
+            NetOptimError::InvalidNode(5)
+        );
+    }
+}
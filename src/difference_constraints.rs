@@ -0,0 +1,378 @@
+//! Difference constraints solver.
+//!
+//! A system of constraints `x_j - x_i <= c` is feasible iff its constraint graph (an
+//! arc `i -> j` of weight `c` per constraint) has no negative cycle. Shortest path
+//! distances from a virtual source then form a feasible assignment. Otherwise the
+//! constraints along a simple negative cycle sum to `0 <= (negative)`. They are a
+//! minimal conflicting set: they alone are infeasible, and every proper subset of them
+//! is feasible.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::{Neg, Sub};
+
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
+
+use crate::error::NetOptimError;
+use crate::weight::Weight;
+use crate::{bellman_ford_virtual_source, BellmanFordError};
+
+/// A single constraint `lhs - rhs <= bound`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DifferenceConstraint<W> {
+    /// The variable with a positive sign.
+    pub lhs: String,
+    /// The variable with a negative sign.
+    pub rhs: String,
+    /// The upper bound on `lhs - rhs`.
+    pub bound: W,
+}
+
+impl<W: fmt::Display> fmt::Display for DifferenceConstraint<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.rhs == DifferenceConstraints::<W>::ZERO {
+            write!(f, "{} <= {}", self.lhs, self.bound)
+        } else if self.lhs == DifferenceConstraints::<W>::ZERO {
+            write!(f, "-{} <= {}", self.rhs, self.bound)
+        } else {
+            write!(f, "{} - {} <= {}", self.lhs, self.rhs, self.bound)
+        }
+    }
+}
+
+/// Infeasibility certificate returned by [`DifferenceConstraints::solve`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Infeasible<W> {
+    /// A minimal set of conflicting constraints, in cycle order: the `lhs` of each
+    /// constraint is the `rhs` of the next one, so their sum is `0 <= (negative)`.
+    pub conflict: Vec<DifferenceConstraint<W>>,
+}
+
+impl<W: fmt::Display> fmt::Display for Infeasible<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "infeasible difference constraints:")?;
+        for (k, constraint) in self.conflict.iter().enumerate() {
+            let sep = if k == 0 { " " } else { ", " };
+            write!(f, "{}{}", sep, constraint)?;
+        }
+        Ok(())
+    }
+}
+
+impl<W: fmt::Debug + fmt::Display> std::error::Error for Infeasible<W> {}
+
+/// Error of [`DifferenceConstraints::solve`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveError<W> {
+    /// The system has no solution.
+    Infeasible(Infeasible<W>),
+    /// A value does not fit the weight type: [`NetOptimError::AlgorithmError`].
+    Error(NetOptimError),
+}
+
+impl<W: fmt::Display> fmt::Display for SolveError<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Infeasible(infeasible) => infeasible.fmt(f),
+            SolveError::Error(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<W: fmt::Debug + fmt::Display> std::error::Error for SolveError<W> {}
+
+/// Builder for a system of difference constraints over named variables.
+///
+/// Bounds on a single variable are expressed against the reserved variable
+/// [`DifferenceConstraints::ZERO`], which is fixed to zero in the solution.
+#[derive(Debug, Clone)]
+pub struct DifferenceConstraints<W> {
+    graph: DiGraph<String, W>,
+    names: HashMap<String, NodeIndex>,
+    // Indexed by edge index: `constraints[k]` is the constraint of edge `k`.
+    constraints: Vec<DifferenceConstraint<W>>,
+}
+
+impl<W> DifferenceConstraints<W> {
+    /// Name of the variable fixed to zero.
+    pub const ZERO: &'static str = "0";
+}
+
+impl<W> Default for DifferenceConstraints<W>
+where
+    W: Weight + Sub<Output = W> + Neg<Output = W>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W> DifferenceConstraints<W>
+where
+    W: Weight + Sub<Output = W> + Neg<Output = W>,
+{
+    /// Creates an empty system containing only the zero variable.
+    pub fn new() -> Self {
+        let mut system = Self {
+            graph: DiGraph::new(),
+            names: HashMap::new(),
+            constraints: Vec::new(),
+        };
+        system.variable(Self::ZERO);
+        system
+    }
+
+    /// Declares a variable, so that it appears in the solution even if unconstrained.
+    pub fn add_variable(&mut self, name: &str) {
+        self.variable(name);
+    }
+
+    /// Adds the constraint `lhs - rhs <= bound`.
+    pub fn add_constraint(&mut self, lhs: &str, rhs: &str, bound: W) {
+        let j = self.variable(lhs);
+        let i = self.variable(rhs);
+        self.graph.add_edge(i, j, bound.clone());
+        self.constraints.push(DifferenceConstraint {
+            lhs: lhs.to_string(),
+            rhs: rhs.to_string(),
+            bound,
+        });
+    }
+
+    /// Adds the constraint `lhs - rhs == value`, as `lhs - rhs <= value` and
+    /// `rhs - lhs <= -value`.
+    pub fn add_equality(&mut self, lhs: &str, rhs: &str, value: W) {
+        self.add_constraint(lhs, rhs, value.clone());
+        self.add_constraint(rhs, lhs, -value);
+    }
+
+    /// Adds the bound `x <= bound`.
+    pub fn add_upper_bound(&mut self, x: &str, bound: W) {
+        self.add_constraint(x, Self::ZERO, bound);
+    }
+
+    /// Adds the bound `x >= bound`.
+    pub fn add_lower_bound(&mut self, x: &str, bound: W) {
+        self.add_constraint(Self::ZERO, x, -bound);
+    }
+
+    /// The constraints added so far.
+    pub fn constraints(&self) -> &[DifferenceConstraint<W>] {
+        &self.constraints
+    }
+
+    /// Solves the system.
+    ///
+    /// Returns:
+    ///
+    /// A feasible value for every variable other than [`DifferenceConstraints::ZERO`],
+    /// [`SolveError::Infeasible`] with a certificate listing a minimal set of conflicting
+    /// constraints, or [`SolveError::Error`] if a value overflows the weight type.
+    ///
+    /// # Example
+    /// ```rust
+    /// use netoptim_rs::difference_constraints::{DifferenceConstraints, SolveError};
+    ///
+    /// let mut system = DifferenceConstraints::new();
+    /// system.add_constraint("b", "a", 3); // b - a <= 3
+    /// system.add_lower_bound("b", 5); // b >= 5
+    /// system.add_upper_bound("a", 4); // a <= 4
+    /// let x = system.solve().unwrap();
+    /// assert!(x["b"] - x["a"] <= 3 && x["b"] >= 5 && x["a"] <= 4);
+    ///
+    /// system.add_upper_bound("a", 1); // now b <= a + 3 <= 4 < 5
+    /// let Err(SolveError::Infeasible(conflict)) = system.solve() else {
+    ///     panic!("the system is infeasible");
+    /// };
+    /// assert_eq!(conflict.conflict.len(), 3);
+    /// assert_eq!(
+    ///     conflict.to_string(),
+    ///     "infeasible difference constraints: a <= 1, b - a <= 3, -b <= -5"
+    /// );
+    /// ```
+    pub fn solve(&self) -> Result<HashMap<String, W>, SolveError<W>> {
+        let distances = match bellman_ford_virtual_source(&self.graph) {
+            Ok(paths) => paths.distances,
+            Err(BellmanFordError::NegativeCycle(cycle)) => {
+                let conflict = cycle
+                    .edges
+                    .iter()
+                    .map(|edge| self.constraints[edge.id().index()].clone())
+                    .collect();
+                return Err(SolveError::Infeasible(Infeasible { conflict }));
+            }
+            Err(BellmanFordError::Error(err)) => {
+                let err = match err {
+                    NetOptimError::AlgorithmError(msg) => NetOptimError::AlgorithmError(msg),
+                    _ => unreachable!("every node of the constraint graph is a source"),
+                };
+                return Err(SolveError::Error(err));
+            }
+        };
+
+        let value = |v: NodeIndex| distances[v.index()].clone().unwrap_or_else(W::zero);
+        let offset = value(self.names[Self::ZERO]);
+        self.names
+            .iter()
+            .filter(|(name, _)| name.as_str() != Self::ZERO)
+            .map(|(name, &v)| match value(v).checked_sub(&offset) {
+                Some(x) => Ok((name.clone(), x)),
+                None => Err(SolveError::Error(NetOptimError::AlgorithmError(format!(
+                    "value of {} overflows",
+                    name
+                )))),
+            })
+            .collect()
+    }
+
+    fn variable(&mut self, name: &str) -> NodeIndex {
+        if let Some(&v) = self.names.get(name) {
+            return v;
+        }
+        let v = self.graph.add_node(name.to_string());
+        self.names.insert(name.to_string(), v);
+        v
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::rational::Ratio;
+
+    fn infeasible<W>(result: Result<HashMap<String, W>, SolveError<W>>) -> Infeasible<W> {
+        match result {
+            Err(SolveError::Infeasible(err)) => err,
+            _ => panic!("expected an infeasible system"),
+        }
+    }
+
+    fn check<W>(system: &DifferenceConstraints<W>, x: &HashMap<String, W>)
+    where
+        W: Weight + Sub<Output = W> + Neg<Output = W>,
+    {
+        let value = |name: &str| {
+            if name == DifferenceConstraints::<W>::ZERO {
+                W::zero()
+            } else {
+                x[name].clone()
+            }
+        };
+        for c in system.constraints() {
+            assert!(value(&c.lhs) - value(&c.rhs) <= c.bound);
+        }
+    }
+
+    #[test]
+    fn test_difference_constraints_feasible() {
+        let mut system = DifferenceConstraints::new();
+        system.add_constraint("x1", "x2", 0);
+        system.add_constraint("x1", "x5", -1);
+        system.add_constraint("x2", "x5", 1);
+        system.add_constraint("x3", "x1", 5);
+        system.add_constraint("x4", "x1", 4);
+        system.add_constraint("x4", "x3", -1);
+        system.add_constraint("x5", "x3", -3);
+        system.add_constraint("x5", "x4", -3);
+        let x = system.solve().unwrap();
+        assert_eq!(x.len(), 5);
+        check(&system, &x);
+    }
+
+    #[test]
+    fn test_difference_constraints_equalities_and_bounds() {
+        let mut system = DifferenceConstraints::new();
+        system.add_equality("end", "start", 7);
+        system.add_lower_bound("start", 2);
+        system.add_upper_bound("end", 20);
+        system.add_variable("free");
+        let x = system.solve().unwrap();
+        check(&system, &x);
+        assert_eq!(x["end"] - x["start"], 7);
+        assert!(x.contains_key("free"));
+        assert!(!x.contains_key(DifferenceConstraints::<i32>::ZERO));
+    }
+
+    #[test]
+    fn test_difference_constraints_zero_is_fixed() {
+        let mut system = DifferenceConstraints::new();
+        system.add_equality("a", DifferenceConstraints::<i64>::ZERO, 3);
+        system.add_constraint("b", "a", -10);
+        let x = system.solve().unwrap();
+        assert_eq!(x["a"], 3);
+        assert!(x["b"] <= -7);
+    }
+
+    #[test]
+    fn test_difference_constraints_minimal_conflict() {
+        let mut system = DifferenceConstraints::new();
+        system.add_constraint("a", "b", 1);
+        system.add_constraint("c", "a", 2);
+        // Not part of the conflict.
+        system.add_constraint("d", "c", 0);
+        system.add_lower_bound("d", -100);
+        system.add_equality("b", "c", -4);
+        let err = infeasible(system.solve());
+        // a - b <= 1, c - a <= 2 and b - c <= -4 (from the equality) sum to 0 <= -1.
+        assert_eq!(err.conflict.len(), 3);
+        let total: i32 = err.conflict.iter().map(|c| c.bound).sum();
+        assert!(total < 0);
+        for k in 0..err.conflict.len() {
+            let next = &err.conflict[(k + 1) % err.conflict.len()];
+            assert_eq!(err.conflict[k].lhs, next.rhs);
+        }
+        assert!(err.conflict.iter().all(|c| c.lhs != "d" && c.rhs != "d"));
+    }
+
+    #[test]
+    fn test_difference_constraints_conflicting_bounds() {
+        let mut system = DifferenceConstraints::new();
+        system.add_lower_bound("x", 3.0);
+        system.add_upper_bound("x", 2.5);
+        let err = infeasible(system.solve());
+        assert_eq!(err.conflict.len(), 2);
+        let text = err.to_string();
+        assert!(text.contains("x <= 2.5"));
+        assert!(text.contains("-x <= -3"));
+    }
+
+    #[test]
+    fn test_difference_constraints_rational() {
+        let r = |n, d| Ratio::new(n, d);
+        let mut system = DifferenceConstraints::new();
+        system.add_constraint("y", "x", r(1, 3));
+        system.add_constraint("z", "y", r(1, 3));
+        system.add_constraint("x", "z", r(-2, 3));
+        let x = system.solve().unwrap();
+        check(&system, &x);
+        assert_eq!(x["z"] - x["x"], r(2, 3));
+    }
+
+    #[test]
+    fn test_difference_constraints_overflow_is_an_error() {
+        // b = -100 and c = -200, which does not fit an i8.
+        let mut system = DifferenceConstraints::<i8>::new();
+        system.add_constraint("b", "a", -100);
+        system.add_constraint("c", "b", -100);
+        assert!(matches!(
+            system.solve(),
+            Err(SolveError::Error(NetOptimError::AlgorithmError(_)))
+        ));
+    }
+
+    #[test]
+    fn test_difference_constraints_value_overflow_is_an_error() {
+        // The distances fit an i8, but b >= a + 64 >= 128 does not.
+        let mut system = DifferenceConstraints::<i8>::new();
+        system.add_constraint("a", "b", -64);
+        system.add_lower_bound("a", 64);
+        let Err(SolveError::Error(err)) = system.solve() else {
+            panic!("b does not fit an i8");
+        };
+        assert_eq!(
+            err,
+            NetOptimError::AlgorithmError("value of b overflows".to_string())
+        );
+    }
+}
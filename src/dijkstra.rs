@@ -1,21 +1,30 @@
 //! Dijkstra's shortest path algorithm implementation.
 
-use petgraph::algo::FloatMeasure;
+use num::{PrimInt, ToPrimitive};
 #[allow(unused_imports)]
 use petgraph::graph::NodeIndex;
 use petgraph::visit::{
-    EdgeRef, IntoEdges, IntoNodeIdentifiers, NodeCount, NodeIndexable, VisitMap, Visitable,
+    EdgeRef, IntoEdges, IntoEdgesDirected, IntoNodeIdentifiers, NodeCount, NodeIndexable, Reversed,
+    VisitMap, Visitable,
 };
+use petgraph::Direction;
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 
+use crate::error::NetOptimError;
+use crate::monotone_queue::MonotoneQueue;
+use crate::weight::Weight;
+use crate::{improved_distance, overflow_error, validate_node, Overflowed};
+
 /// Result of Dijkstra's shortest path algorithm.
 ///
-/// Contains the distances from the source node to all other nodes,
-/// and the predecessor of each node along the shortest path.
+/// Contains the distances from the source node to all other nodes (`None` for
+/// unreachable nodes), and the predecessor of each node along the shortest path.
+/// Both are indexed by `NodeIndexable::to_index` and sized by `node_bound()`, so
+/// vacant indices of a graph with removed nodes are `None`.
 #[derive(Debug, Clone)]
 pub struct DijkstraResult<NodeId, EdgeWeight> {
-    pub distances: Vec<EdgeWeight>,
+    pub distances: Vec<Option<EdgeWeight>>,
     pub predecessors: Vec<Option<NodeId>>,
 }
 
@@ -35,9 +44,9 @@ impl<NodeId: PartialEq, Cost: PartialEq> PartialEq for State<NodeId, Cost> {
 
 impl<NodeId: PartialEq, Cost: PartialEq> Eq for State<NodeId, Cost> {}
 
-impl<NodeId: PartialEq, Cost: FloatMeasure> Ord for State<NodeId, Cost> {
+impl<NodeId: PartialEq, Cost: Weight> Ord for State<NodeId, Cost> {
     fn cmp(&self, other: &Self) -> Ordering {
-        // Use partial_cmp and unwrap since FloatMeasure guarantees total ordering via NaN handling
+        // Incomparable costs (NaN) are treated as equal
         other
             .cost
             .partial_cmp(&self.cost)
@@ -45,7 +54,7 @@ impl<NodeId: PartialEq, Cost: FloatMeasure> Ord for State<NodeId, Cost> {
     }
 }
 
-impl<NodeId: PartialEq, Cost: FloatMeasure> PartialOrd for State<NodeId, Cost> {
+impl<NodeId: PartialEq, Cost: Weight> PartialOrd for State<NodeId, Cost> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
@@ -54,15 +63,21 @@ impl<NodeId: PartialEq, Cost: FloatMeasure> PartialOrd for State<NodeId, Cost> {
 /// \[Generic\] Compute shortest paths from node `source` to all other nodes using Dijkstra's algorithm.
 ///
 /// This implementation uses a binary heap for efficient priority queue operations.
-/// The algorithm requires non-negative edge weights.
+/// The algorithm requires non-negative edge weights. Any [`Weight`] can be used, so
+/// integer and rational costs are computed exactly. A node that can only be reached
+/// with a distance that overflows the weight type is reported as
+/// [`NetOptimError::AlgorithmError`] instead of as unreachable.
 ///
 /// # Arguments
 /// * `g` - The graph to compute shortest paths on
 /// * `source` - The source node index
 ///
 /// # Returns
-/// * `Ok(DijkstraResult)` - Contains distances and predecessors for each node
-/// * `Err` - If a negative edge weight is found
+/// * `Ok(DijkstraResult)` - Contains distances (`None` if unreachable) and predecessors for each node
+/// * `Err(NetOptimError::NegativeWeight)` - With the first negative edge found
+/// * `Err(NetOptimError::InvalidNode)` - If `source` is not a node of `g`
+/// * `Err(NetOptimError::AlgorithmError)` - If a node is reachable only with a distance that
+///   overflows the weight type
 ///
 /// # Example
 /// ```rust
@@ -86,51 +101,426 @@ impl<NodeId: PartialEq, Cost: FloatMeasure> PartialOrd for State<NodeId, Cost> {
 /// let result = dijkstra(&g, a);
 /// assert!(result.is_ok());
 /// let paths = result.unwrap();
-/// assert_eq!(paths.distances[a.index()], 0.0);
-/// assert_eq!(paths.distances[b.index()], 2.0);
-/// assert_eq!(paths.distances[c.index()], 3.0);
+/// assert_eq!(paths.distances[a.index()], Some(0.0));
+/// assert_eq!(paths.distances[b.index()], Some(2.0));
+/// assert_eq!(paths.distances[c.index()], Some(3.0));
 /// ```
+#[allow(clippy::type_complexity)]
 pub fn dijkstra<G>(
     g: G,
     source: G::NodeId,
-) -> Result<DijkstraResult<G::NodeId, G::EdgeWeight>, String>
+) -> Result<DijkstraResult<G::NodeId, G::EdgeWeight>, NetOptimError<G::NodeId>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable + Visitable,
+    G::EdgeWeight: Weight,
+{
+    dijkstra_by(
+        g,
+        source,
+        |edge| Ok(Some(edge.weight().clone())),
+        &DijkstraOptions::new(),
+    )
+}
+
+/// Stopping rules for [`dijkstra_with_options`].
+///
+/// By default the search settles every reachable node, like [`dijkstra`].
+#[derive(Debug, Clone)]
+pub struct DijkstraOptions<NodeId, EdgeWeight> {
+    targets: Option<Vec<NodeId>>,
+    radius: Option<EdgeWeight>,
+}
+
+impl<NodeId, EdgeWeight> Default for DijkstraOptions<NodeId, EdgeWeight> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<NodeId, EdgeWeight> DijkstraOptions<NodeId, EdgeWeight> {
+    /// Creates options that settle every reachable node.
+    pub fn new() -> Self {
+        Self {
+            targets: None,
+            radius: None,
+        }
+    }
+
+    /// Stops as soon as every node in `targets` is settled. With no targets, only the
+    /// source is settled.
+    pub fn with_targets<I: IntoIterator<Item = NodeId>>(mut self, targets: I) -> Self {
+        self.targets = Some(targets.into_iter().collect());
+        self
+    }
+
+    /// Only settles nodes whose distance is at most `radius`.
+    pub fn with_radius(mut self, radius: EdgeWeight) -> Self {
+        self.radius = Some(radius);
+        self
+    }
+}
+
+/// \[Generic\] Compute shortest paths from node `source` using Dijkstra's algorithm,
+/// stopping early according to `options`.
+///
+/// The search stops once every target is settled, or once the next node to settle is
+/// farther than the radius, whichever comes first. Only the settled region is
+/// returned: nodes that were not settled have `None` as distance and predecessor,
+/// even if they had a tentative label. Settled distances are exact.
+///
+/// # Arguments
+/// * `g` - The graph to compute shortest paths on
+/// * `source` - The source node index
+/// * `options` - Target set and radius at which to stop
+///
+/// # Returns
+/// * `Ok(DijkstraResult)` - Distances and predecessors of the settled nodes
+/// * `Err(NetOptimError::InvalidNode)` - If a target is not a node of `g`
+/// * `Err` - Otherwise as for [`dijkstra`]
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::prelude::*;
+/// use netoptim_rs::dijkstra::{dijkstra_with_options, DijkstraOptions};
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 2), (1, 2, 2), (2, 3, 2), (3, 4, 2)]);
+/// let a = NodeIndex::new(0);
+///
+/// // Isochrone: everything within distance 4.
+/// let options = DijkstraOptions::new().with_radius(4);
+/// let paths = dijkstra_with_options(&g, a, &options).unwrap();
+/// assert_eq!(paths.distances, vec![Some(0), Some(2), Some(4), None, None]);
+///
+/// // Stop once node 1 is settled.
+/// let options = DijkstraOptions::new().with_targets([NodeIndex::new(1)]);
+/// let paths = dijkstra_with_options(&g, a, &options).unwrap();
+/// assert_eq!(paths.distances, vec![Some(0), Some(2), None, None, None]);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn dijkstra_with_options<G>(
+    g: G,
+    source: G::NodeId,
+    options: &DijkstraOptions<G::NodeId, G::EdgeWeight>,
+) -> Result<DijkstraResult<G::NodeId, G::EdgeWeight>, NetOptimError<G::NodeId>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable + Visitable,
+    G::EdgeWeight: Weight,
+{
+    dijkstra_by(g, source, |edge| Ok(Some(edge.weight().clone())), options)
+}
+
+/// \[Generic\] Compute shortest paths from node `source` using Dijkstra's algorithm on
+/// the costs reduced by node `potentials`.
+///
+/// Edge weights may be negative, as long as the potentials are feasible: every
+/// reduced cost `w(u, v) + p(u) - p(v)` is non-negative. Shortest path distances from
+/// any source, such as those of [`bellman_ford`](crate::bellman_ford) or the `dist` of
+/// [`NegCycleFinder::howard`](crate::neg_cycle::NegCycleFinder::howard) when it finds
+/// no cycle, are feasible. The search runs on the reduced costs, and the returned
+/// distances are true distances in `g`. Unlike [`dijkstra`], negative weights are not
+/// rejected, only negative reduced costs. With floating point weights, a reduced cost
+/// that rounds to slightly below zero is rejected too.
+///
+/// # Arguments
+/// * `g` - The graph to compute shortest paths on
+/// * `source` - The source node index
+/// * `potentials` - A potential per node, indexed by `NodeIndexable::to_index`
+///
+/// # Returns
+/// * `Ok(DijkstraResult)` - Contains distances (`None` if unreachable) and predecessors for each node
+/// * `Err(NetOptimError::NegativeReducedCost)` - With the first edge whose reduced cost is negative
+/// * `Err(NetOptimError::AlgorithmError)` - If `potentials` has fewer than `node_bound()`
+///   entries, or if a reduced cost or a distance overflows the weight type
+/// * `Err(NetOptimError::InvalidNode)` - If `source` is not a node of `g`
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::prelude::*;
+/// use netoptim_rs::dijkstra::dijkstra_with_potentials;
+/// use netoptim_rs::{bellman_ford_virtual_source, NetOptimError};
+///
+/// let g = Graph::<(), i32>::from_edges(&[(0, 1, 4), (0, 2, 1), (2, 1, -2), (1, 3, 1), (2, 3, 5)]);
+/// let potentials: Vec<i32> = bellman_ford_virtual_source(&g)
+///     .unwrap()
+///     .distances
+///     .into_iter()
+///     .map(Option::unwrap)
+///     .collect();
+/// assert_eq!(potentials, vec![0, -2, 0, -1]);
+///
+/// let a = NodeIndex::new(0);
+/// let paths = dijkstra_with_potentials(&g, a, &potentials).unwrap();
+/// assert_eq!(paths.distances, vec![Some(0), Some(-1), Some(1), Some(0)]);
+///
+/// // Zero potentials leave the arc 2 -> 1 with a negative reduced cost.
+/// let err = dijkstra_with_potentials(&g, a, &[0; 4]).unwrap_err();
+/// assert_eq!(
+///     err,
+///     NetOptimError::NegativeReducedCost { source: NodeIndex::new(2), target: NodeIndex::new(1) }
+/// );
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn dijkstra_with_potentials<G>(
+    g: G,
+    source: G::NodeId,
+    potentials: &[G::EdgeWeight],
+) -> Result<DijkstraResult<G::NodeId, G::EdgeWeight>, NetOptimError<G::NodeId>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable + Visitable,
+    G::EdgeWeight: Weight,
+{
+    validate_node(g, source)?;
+    if potentials.len() < g.node_bound() {
+        return Err(NetOptimError::AlgorithmError(format!(
+            "expected {} potentials, got {}",
+            g.node_bound(),
+            potentials.len()
+        )));
+    }
+    let ix = |i| g.to_index(i);
+    let p = |v| &potentials[ix(v)];
+
+    // w(u, v) + p(u) - p(v), checked for sign before subtracting so that unsigned
+    // weights report infeasible potentials instead of wrapping.
+    let reduced_cost = |edge: G::EdgeRef| {
+        let (u, v) = (edge.source(), edge.target());
+        let overflow = || {
+            NetOptimError::AlgorithmError(format!(
+                "reduced cost of edge {} -> {} overflows",
+                ix(u),
+                ix(v)
+            ))
+        };
+        let lifted = edge.weight().checked_add(p(u)).ok_or_else(overflow)?;
+        if lifted < *p(v) {
+            return Err(NetOptimError::NegativeReducedCost {
+                source: u,
+                target: v,
+            });
+        }
+        lifted.checked_sub(p(v)).map(Some).ok_or_else(overflow)
+    };
+    let mut result = dijkstra_by(g, source, reduced_cost, &DijkstraOptions::new())?;
+
+    // Undo the reweighting: d(s, v) = d'(s, v) - p(s) + p(v), in whichever order keeps
+    // the intermediate value in range.
+    for v in g.node_identifiers() {
+        let d = &mut result.distances[ix(v)];
+        if let Some(reduced) = d.take() {
+            let distance = reduced
+                .checked_add(p(v))
+                .and_then(|d| d.checked_sub(p(source)))
+                .or_else(|| reduced.checked_sub(p(source))?.checked_add(p(v)))
+                .ok_or_else(|| {
+                    NetOptimError::AlgorithmError(format!("distance to node {} overflows", ix(v)))
+                })?;
+            *d = Some(distance);
+        }
+    }
+    Ok(result)
+}
+
+/// \[Generic\] Compute shortest paths from node `source` using Dijkstra's algorithm with
+/// a monotone integer priority queue.
+///
+/// For primitive integer weights only. Distances are used as `u64` keys of `queue`,
+/// an empty [`BucketQueue`](crate::monotone_queue::BucketQueue) or
+/// [`RadixHeap`](crate::monotone_queue::RadixHeap) (or another [`MonotoneQueue`]), which
+/// beat the binary heap of [`dijkstra`] on graphs with small integer weights: Dial's
+/// buckets run in O(|E| + |V|·C) for a maximal weight `C`, and the radix heap in
+/// O(|E| + |V| log C). The result is the same as that of [`dijkstra`], up to ties
+/// between predecessors.
+///
+/// # Arguments
+/// * `g` - The graph to compute shortest paths on
+/// * `source` - The source node index
+/// * `queue` - An empty monotone queue
+///
+/// # Returns
+/// * `Ok(DijkstraResult)` - Contains distances (`None` if unreachable) and predecessors for each node
+/// * `Err(NetOptimError::AlgorithmError)` - If a distance of a wider type, such as `u128`,
+///   exceeds `u64::MAX`
+/// * `Err` - Otherwise as for [`dijkstra`]
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::prelude::*;
+/// use netoptim_rs::dijkstra::dijkstra_with_queue;
+/// use netoptim_rs::monotone_queue::{BucketQueue, RadixHeap};
+///
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 2), (0, 2, 7), (1, 2, 3), (2, 3, 1)]);
+/// let a = NodeIndex::new(0);
+///
+/// let paths = dijkstra_with_queue(&g, a, BucketQueue::new()).unwrap();
+/// assert_eq!(paths.distances, vec![Some(0), Some(2), Some(5), Some(6)]);
+/// let paths = dijkstra_with_queue(&g, a, RadixHeap::new()).unwrap();
+/// assert_eq!(paths.distances, vec![Some(0), Some(2), Some(5), Some(6)]);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn dijkstra_with_queue<G, Q>(
+    g: G,
+    source: G::NodeId,
+    mut queue: Q,
+) -> Result<DijkstraResult<G::NodeId, G::EdgeWeight>, NetOptimError<G::NodeId>>
 where
     G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable + Visitable,
-    G::EdgeWeight: FloatMeasure,
+    G::EdgeWeight: Weight + PrimInt,
+    Q: MonotoneQueue<G::NodeId>,
 {
+    validate_node(g, source)?;
+    debug_assert!(queue.is_empty(), "the queue must start empty");
     let ix = |i| g.to_index(i);
-    let node_count = g.node_count();
+    let zero = <G::EdgeWeight as Weight>::zero();
+
+    let mut distances: Vec<Option<G::EdgeWeight>> = vec![None; g.node_bound()];
+    let mut predecessors = vec![None; g.node_bound()];
+    let mut visited = g.visit_map();
+
+    distances[ix(source)] = Some(zero);
+    queue.push(0, source);
+    let mut overflowed = Overflowed::default();
+
+    while let Some((_, node)) = queue.pop() {
+        if !visited.visit(node) {
+            continue;
+        }
+        let cost = distances[ix(node)].expect("queued nodes have a distance");
+
+        for edge in g.edges(node) {
+            let target = edge.target();
+            let weight = *edge.weight();
+
+            if weight < zero {
+                return Err(NetOptimError::NegativeWeight {
+                    source: node,
+                    target,
+                });
+            }
 
-    let mut distances = vec![<_>::infinite(); node_count];
-    let mut predecessors = vec![None; node_count];
+            let edge_ix = (ix(node), ix(target));
+            let relaxed = improved_distance(
+                &cost,
+                &distances[ix(target)],
+                &weight,
+                edge_ix,
+                &mut overflowed,
+            );
+            if let Some(new_cost) = relaxed? {
+                // Non-negative, but wider types such as `u128` may exceed a `u64`.
+                let key = new_cost.to_u64().ok_or_else(|| {
+                    NetOptimError::AlgorithmError(format!(
+                        "distance to node {} does not fit a u64 queue key",
+                        ix(target)
+                    ))
+                })?;
+                distances[ix(target)] = Some(new_cost);
+                predecessors[ix(target)] = Some(node);
+                queue.push(key, target);
+            }
+        }
+    }
+
+    overflowed.check(&distances)?;
+    Ok(DijkstraResult {
+        distances,
+        predecessors,
+    })
+}
+
+// Dijkstra's algorithm with edge costs given by `edge_cost`; edges whose cost is
+// `None` are ignored, and an error from `edge_cost` aborts the search.
+pub(crate) fn dijkstra_by<G, W, F>(
+    g: G,
+    source: G::NodeId,
+    mut edge_cost: F,
+    options: &DijkstraOptions<G::NodeId, W>,
+) -> Result<DijkstraResult<G::NodeId, W>, NetOptimError<G::NodeId>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable + Visitable,
+    W: Weight,
+    F: FnMut(G::EdgeRef) -> Result<Option<W>, NetOptimError<G::NodeId>>,
+{
+    validate_node(g, source)?;
+    let ix = |i| g.to_index(i);
+    // Indexed by `to_index`, so sized by `node_bound`: graphs with index holes (such as
+    // a `StableGraph` after removals) have more slots than nodes.
+    let mut distances: Vec<Option<W>> = vec![None; g.node_bound()];
+    let mut predecessors = vec![None; g.node_bound()];
     let mut visited = g.visit_map();
 
-    distances[ix(source)] = <_>::zero();
+    // Targets that are not settled yet.
+    let mut is_target = vec![false; g.node_bound()];
+    let mut remaining = 0;
+    for &t in options.targets.iter().flatten() {
+        validate_node(g, t)?;
+        if !is_target[ix(t)] {
+            is_target[ix(t)] = true;
+            remaining += 1;
+        }
+    }
+    let mut stopped_early = false;
+
+    distances[ix(source)] = Some(W::zero());
+    // An empty target set is settled at once: only the source is known.
+    if options.targets.as_ref().is_some_and(Vec::is_empty) {
+        return Ok(DijkstraResult {
+            distances,
+            predecessors,
+        });
+    }
 
     let mut heap = BinaryHeap::new();
     heap.push(State {
         node: source,
-        cost: <_>::zero(),
+        cost: W::zero(),
     });
+    let mut overflowed = Overflowed::default();
 
     while let Some(State { node, cost }) = heap.pop() {
         if visited.is_visited(&node) {
             continue;
         }
+        if options.radius.as_ref().is_some_and(|radius| *radius < cost) {
+            stopped_early = true;
+            break;
+        }
 
         visited.visit(node);
+        if is_target[ix(node)] {
+            is_target[ix(node)] = false;
+            remaining -= 1;
+            if remaining == 0 {
+                stopped_early = true;
+                break;
+            }
+        }
 
         for edge in g.edges(node) {
             let target = edge.target();
-            let weight = *edge.weight();
+            let Some(weight) = edge_cost(edge)? else {
+                continue;
+            };
 
-            if weight < <_>::zero() {
-                return Err("Dijkstra's algorithm requires non-negative edge weights".to_string());
+            if weight < W::zero() {
+                return Err(NetOptimError::NegativeWeight {
+                    source: node,
+                    target,
+                });
             }
 
-            let new_cost = cost + weight;
-            if new_cost < distances[ix(target)] {
-                distances[ix(target)] = new_cost;
+            let edge_ix = (ix(node), ix(target));
+            let relaxed = improved_distance(
+                &cost,
+                &distances[ix(target)],
+                &weight,
+                edge_ix,
+                &mut overflowed,
+            );
+            if let Some(new_cost) = relaxed? {
+                distances[ix(target)] = Some(new_cost.clone());
                 predecessors[ix(target)] = Some(node);
                 heap.push(State {
                     node: target,
@@ -140,6 +530,19 @@ where
         }
     }
 
+    // Drop the tentative labels outside the settled region. Past the radius or the
+    // last target, a node left unlabeled by an overflow is outside it anyway.
+    if stopped_early {
+        for v in g.node_identifiers() {
+            if !visited.is_visited(&v) {
+                distances[ix(v)] = None;
+                predecessors[ix(v)] = None;
+            }
+        }
+    } else {
+        overflowed.check(&distances)?;
+    }
+
     Ok(DijkstraResult {
         distances,
         predecessors,
@@ -154,14 +557,16 @@ where
 /// * `target` - The target node index
 ///
 /// # Returns
-/// * `Some(path)` - A vector of node indices representing the shortest path from source to target
-/// * `None` - If no path exists
+/// * `Ok(path)` - A vector of node indices representing the shortest path from source to target
+/// * `Err(NetOptimError::NoPath)` - If `target` is not reachable
+/// * `Err` - Otherwise as for [`dijkstra`], or if `target` is not a node of `g`
 ///
 /// # Example
 /// ```rust
 /// use petgraph::Graph;
 /// use petgraph::prelude::*;
 /// use netoptim_rs::dijkstra::dijkstra_path;
+/// use netoptim_rs::NetOptimError;
 ///
 /// let mut g = Graph::new();
 /// let a = g.add_node(());
@@ -170,38 +575,436 @@ where
 /// g.extend_with_edges(&[(0, 1, 2.0), (1, 2, 3.0)]);
 ///
 /// let path = dijkstra_path(&g, a, c);
-/// assert_eq!(path, Some(vec![a, b, c]));
+/// assert_eq!(path, Ok(vec![a, b, c]));
+/// assert_eq!(dijkstra_path(&g, c, a), Err(NetOptimError::NoPath));
 /// ```
-pub fn dijkstra_path<G>(g: G, source: G::NodeId, target: G::NodeId) -> Option<Vec<G::NodeId>>
+pub fn dijkstra_path<G>(
+    g: G,
+    source: G::NodeId,
+    target: G::NodeId,
+) -> Result<Vec<G::NodeId>, NetOptimError<G::NodeId>>
 where
     G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable + Visitable,
-    G::EdgeWeight: FloatMeasure,
+    G::EdgeWeight: Weight,
     G::NodeId: PartialEq,
 {
     let ix = |i| g.to_index(i);
+    validate_node(g, source)?;
+    validate_node(g, target)?;
 
-    let result = dijkstra(g, source).ok()?;
+    let result = dijkstra(g, source)?;
 
     if result.predecessors[ix(target)].is_none() && source != target {
-        return None;
+        return Err(NetOptimError::NoPath);
     }
 
     let mut path = vec![target];
     let mut current = target;
 
     while current != source {
-        let pred = result.predecessors[ix(current)]?;
+        let pred = result.predecessors[ix(current)].ok_or(NetOptimError::NoPath)?;
         path.push(pred);
         current = pred;
     }
 
     path.reverse();
-    Some(path)
+    Ok(path)
+}
+
+/// \[Generic\] Compute a shortest path from `source` to `target` using the
+/// [A* search algorithm][astar].
+///
+/// Nodes are scanned in order of `distance + heuristic(node)`, and the search stops
+/// as soon as `target` is scanned, so only the part of the graph "towards" the target
+/// is explored. Like [`dijkstra`], edge weights must be non-negative.
+///
+/// The heuristic must be *consistent*: `heuristic(u) <= w(u, v) + heuristic(v)` for
+/// every edge, and `heuristic(target) == 0`. It then never overestimates the remaining
+/// distance, and the returned path is optimal. A zero heuristic turns A* into
+/// Dijkstra's algorithm. In debug builds every scanned edge is checked, and an
+/// inconsistent heuristic panics.
+///
+/// [astar]: https://en.wikipedia.org/wiki/A*_search_algorithm
+///
+/// # Arguments
+/// * `g` - The graph to search
+/// * `source` - The source node index
+/// * `target` - The target node index
+/// * `heuristic` - An estimate of the distance from a node to `target`
+///
+/// # Returns
+/// * `Ok((cost, path))` - The cost of a shortest path and its nodes, from source to target
+/// * `Err(NetOptimError::NoPath)` - If `target` is not reachable
+/// * `Err` - Otherwise as for [`dijkstra_path`]
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::prelude::*;
+/// use netoptim_rs::dijkstra::astar;
+///
+/// // A 3x3 grid with unit edges; node `3 * row + col`.
+/// let mut g = Graph::<(), u32>::new();
+/// let nodes: Vec<_> = (0..9).map(|_| g.add_node(())).collect();
+/// for r in 0..3 {
+///     for c in 0..3 {
+///         if c + 1 < 3 {
+///             g.add_edge(nodes[3 * r + c], nodes[3 * r + c + 1], 1);
+///         }
+///         if r + 1 < 3 {
+///             g.add_edge(nodes[3 * r + c], nodes[3 * (r + 1) + c], 1);
+///         }
+///     }
+/// }
+///
+/// // Manhattan distance to the bottom-right corner.
+/// let target = nodes[8];
+/// let manhattan = |v: NodeIndex| (2 - v.index() as u32 / 3) + (2 - v.index() as u32 % 3);
+/// let (cost, path) = astar(&g, nodes[0], target, manhattan).unwrap();
+/// assert_eq!(cost, 4);
+/// assert_eq!(path.first(), Some(&nodes[0]));
+/// assert_eq!(path.last(), Some(&target));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn astar<G, H>(
+    g: G,
+    source: G::NodeId,
+    target: G::NodeId,
+    mut heuristic: H,
+) -> Result<(G::EdgeWeight, Vec<G::NodeId>), NetOptimError<G::NodeId>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable + Visitable,
+    G::EdgeWeight: Weight,
+    H: FnMut(G::NodeId) -> G::EdgeWeight,
+{
+    validate_node(g, source)?;
+    validate_node(g, target)?;
+    let ix = |i| g.to_index(i);
+    let zero = G::EdgeWeight::zero();
+
+    debug_assert!(
+        heuristic(target).partial_cmp(&zero) != Some(Ordering::Greater),
+        "A* heuristic must be zero at the target"
+    );
+
+    let mut distances: Vec<Option<G::EdgeWeight>> = vec![None; g.node_bound()];
+    let mut predecessors = vec![None; g.node_bound()];
+    let mut visited = g.visit_map();
+
+    distances[ix(source)] = Some(zero.clone());
+
+    let mut heap = BinaryHeap::new();
+    heap.push(State {
+        node: source,
+        cost: heuristic(source),
+    });
+    let mut overflowed = Overflowed::default();
+
+    while let Some(State { node, .. }) = heap.pop() {
+        if node == target {
+            let cost = distances[ix(target)].clone().expect("target was reached");
+            let mut path = vec![target];
+            let mut current = target;
+            while current != source {
+                current = predecessors[ix(current)].expect("reached nodes have a predecessor");
+                path.push(current);
+            }
+            path.reverse();
+            return Ok((cost, path));
+        }
+
+        if !visited.visit(node) {
+            continue;
+        }
+
+        let cost = distances[ix(node)]
+            .clone()
+            .expect("queued nodes have a distance");
+        #[cfg(debug_assertions)]
+        let node_estimate = heuristic(node);
+
+        for edge in g.edges(node) {
+            let next = edge.target();
+            let weight = edge.weight();
+
+            if *weight < zero {
+                return Err(NetOptimError::NegativeWeight {
+                    source: node,
+                    target: next,
+                });
+            }
+
+            let next_estimate = heuristic(next);
+            #[cfg(debug_assertions)]
+            assert!(
+                consistent(&node_estimate, weight, &next_estimate),
+                "A* heuristic is not consistent on an edge: h(u) > w(u, v) + h(v)"
+            );
+
+            if visited.is_visited(&next) {
+                continue;
+            }
+            let edge_ix = (ix(node), ix(next));
+            let relaxed = improved_distance(
+                &cost,
+                &distances[ix(next)],
+                weight,
+                edge_ix,
+                &mut overflowed,
+            );
+            if let Some(new_cost) = relaxed? {
+                // Every path to the target through `next` is at least this long.
+                let Some(priority) = new_cost.checked_add(&next_estimate) else {
+                    overflowed.push(edge_ix);
+                    continue;
+                };
+                distances[ix(next)] = Some(new_cost);
+                predecessors[ix(next)] = Some(node);
+                heap.push(State {
+                    node: next,
+                    cost: priority,
+                });
+            }
+        }
+    }
+
+    overflowed.check(&distances)?;
+    Err(NetOptimError::NoPath)
+}
+
+/// \[Generic\] Compute shortest path from `source` to `target` using bidirectional
+/// Dijkstra's algorithm.
+///
+/// A forward search from `source` and a backward search from `target` (over the
+/// [`Reversed`] graph) take turns, each time advancing the side with the smaller
+/// tentative distance. Every scanned edge that reaches a node labeled by the other side
+/// closes a candidate path, and the search stops once the two smallest tentative
+/// distances add up to at least the best candidate. On road-like graphs each side only
+/// covers about half the distance, far fewer nodes than a single search from `source`.
+///
+/// # Arguments
+/// * `g` - The graph to compute shortest path on
+/// * `source` - The source node index
+/// * `target` - The target node index
+///
+/// # Returns
+/// * `Ok(path)` - A vector of node indices representing the shortest path from source to target
+/// * `Err` - As for [`dijkstra_path`]
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::prelude::*;
+/// use netoptim_rs::dijkstra::bidirectional_dijkstra;
+/// use netoptim_rs::NetOptimError;
+///
+/// let mut g = Graph::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// let c = g.add_node(());
+/// let d = g.add_node(());
+/// g.extend_with_edges(&[(0, 1, 2.0), (1, 2, 3.0), (0, 3, 1.0), (3, 2, 5.0)]);
+///
+/// let path = bidirectional_dijkstra(&g, a, c);
+/// assert_eq!(path, Ok(vec![a, b, c]));
+/// assert_eq!(bidirectional_dijkstra(&g, c, a), Err(NetOptimError::NoPath));
+/// ```
+pub fn bidirectional_dijkstra<G>(
+    g: G,
+    source: G::NodeId,
+    target: G::NodeId,
+) -> Result<Vec<G::NodeId>, NetOptimError<G::NodeId>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdgesDirected + NodeIndexable,
+    G::EdgeWeight: Weight,
+{
+    validate_node(g, source)?;
+    validate_node(g, target)?;
+    let ix = |i| g.to_index(i);
+    if source == target {
+        return Ok(vec![source]);
+    }
+
+    let mut forward = Search::<_, G::EdgeWeight>::new(g, source);
+    let mut backward = Search::<_, G::EdgeWeight>::new(g, target);
+    // Length of the best path found so far and the node where its two halves meet.
+    let mut best: Option<(G::EdgeWeight, G::NodeId)> = None;
+
+    while let (Some(f), Some(b)) = (forward.min_cost(g), backward.min_cost(g)) {
+        // No path through a node that is not settled on either side can be shorter.
+        if let Some((best_cost, _)) = &best {
+            if f.checked_add(&b).is_none_or(|bound| bound >= *best_cost) {
+                break;
+            }
+        }
+        if f <= b {
+            forward.step(g, Direction::Outgoing, &backward, &mut best)?;
+        } else {
+            backward.step(Reversed(g), Direction::Incoming, &forward, &mut best)?;
+        }
+    }
+
+    if best.is_none() {
+        // A side that ran out of nodes has labeled every node it can reach without
+        // overflow, and an overflowing meeting is a path that is too long.
+        for side in [&forward, &backward] {
+            if side.heap.is_empty() {
+                side.overflowed.check(&side.distances)?;
+            }
+            if let Some(edge) = side.overflowed_meeting {
+                return Err(overflow_error(edge));
+            }
+        }
+    }
+
+    // Join the two halves at the meeting node.
+    let (_, meeting) = best.ok_or(NetOptimError::NoPath)?;
+    let mut path = vec![meeting];
+    let mut current = meeting;
+    while let Some(pred) = forward.predecessors[ix(current)] {
+        path.push(pred);
+        current = pred;
+    }
+    path.reverse();
+    current = meeting;
+    while let Some(next) = backward.predecessors[ix(current)] {
+        path.push(next);
+        current = next;
+    }
+    Ok(path)
+}
+
+// One side of a bidirectional search. On the backward side distances are to the
+// target, and `predecessors` point to the next node towards it.
+struct Search<NodeId, W> {
+    distances: Vec<Option<W>>,
+    predecessors: Vec<Option<NodeId>>,
+    settled: Vec<bool>,
+    heap: BinaryHeap<State<NodeId, W>>,
+    overflowed: Overflowed,
+    // An edge, as input indices, closing a path whose length overflows.
+    overflowed_meeting: Option<(usize, usize)>,
+}
+
+impl<NodeId: Copy + PartialEq, W: Weight> Search<NodeId, W> {
+    fn new<G>(g: G, start: NodeId) -> Self
+    where
+        G: NodeIndexable<NodeId = NodeId>,
+    {
+        let mut distances = vec![None; g.node_bound()];
+        distances[g.to_index(start)] = Some(W::zero());
+        let mut heap = BinaryHeap::new();
+        heap.push(State {
+            node: start,
+            cost: W::zero(),
+        });
+        Search {
+            distances,
+            predecessors: vec![None; g.node_bound()],
+            settled: vec![false; g.node_bound()],
+            heap,
+            overflowed: Overflowed::default(),
+            overflowed_meeting: None,
+        }
+    }
+
+    // Smallest tentative distance of a node that is not settled yet, dropping stale
+    // heap entries on the way.
+    fn min_cost<G>(&mut self, g: G) -> Option<W>
+    where
+        G: NodeIndexable<NodeId = NodeId>,
+    {
+        while let Some(top) = self.heap.peek() {
+            if !self.settled[g.to_index(top.node)] {
+                return Some(top.cost.clone());
+            }
+            self.heap.pop();
+        }
+        None
+    }
+
+    // Settle the closest node and relax its edges in `g`, recording in `best` any
+    // shorter path that meets the `other` side. `direction` is `Incoming` when `g` is
+    // the reversed input graph.
+    fn step<G>(
+        &mut self,
+        g: G,
+        direction: Direction,
+        other: &Search<NodeId, W>,
+        best: &mut Option<(W, NodeId)>,
+    ) -> Result<(), NetOptimError<NodeId>>
+    where
+        G: IntoEdges<NodeId = NodeId, EdgeWeight = W> + NodeIndexable,
+    {
+        let ix = |i| g.to_index(i);
+        let Some(State { node, cost }) = self.heap.pop() else {
+            return Ok(());
+        };
+        self.settled[ix(node)] = true;
+
+        for edge in g.edges(node) {
+            let next = edge.target();
+            let weight = edge.weight();
+
+            // On the backward side the edge runs from `next` to `node` in the input.
+            let (source, target) = match direction {
+                Direction::Outgoing => (edge.source(), edge.target()),
+                Direction::Incoming => (edge.target(), edge.source()),
+            };
+            if *weight < W::zero() {
+                return Err(NetOptimError::NegativeWeight { source, target });
+            }
+            if self.settled[ix(next)] {
+                continue;
+            }
+            let edge_ix = (ix(source), ix(target));
+            let relaxed = improved_distance(
+                &cost,
+                &self.distances[ix(next)],
+                weight,
+                edge_ix,
+                &mut self.overflowed,
+            );
+            if let Some(new_cost) = relaxed? {
+                self.distances[ix(next)] = Some(new_cost.clone());
+                self.predecessors[ix(next)] = Some(node);
+                self.heap.push(State {
+                    node: next,
+                    cost: new_cost,
+                });
+            }
+
+            let (Some(here), Some(there)) = (&self.distances[ix(next)], &other.distances[ix(next)])
+            else {
+                continue;
+            };
+            let Some(total) = here.checked_add(there) else {
+                self.overflowed_meeting = Some(edge_ix);
+                continue;
+            };
+            if best
+                .as_ref()
+                .is_none_or(|(best_cost, _)| total < *best_cost)
+            {
+                *best = Some((total, next));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Whether `h(u) <= w(u, v) + h(v)`. Overflowing sums are unbounded and always pass.
+#[cfg(debug_assertions)]
+fn consistent<W: Weight>(source_estimate: &W, weight: &W, target_estimate: &W) -> bool {
+    weight
+        .checked_add(target_estimate)
+        .is_none_or(|bound| source_estimate.partial_cmp(&bound) != Some(Ordering::Greater))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{grid_graph, random_graph, Lcg};
     use petgraph::Graph;
 
     #[test]
@@ -216,17 +1019,64 @@ mod tests {
         let result = dijkstra(&g, a);
         assert!(result.is_ok());
         let paths = result.unwrap();
-        assert_eq!(paths.distances[0], 0.0);
-        assert_eq!(paths.distances[1], 2.0);
-        assert_eq!(paths.distances[2], 3.0);
-        assert_eq!(paths.distances[3], 4.0);
+        assert_eq!(paths.distances[0], Some(0.0));
+        assert_eq!(paths.distances[1], Some(2.0));
+        assert_eq!(paths.distances[2], Some(3.0));
+        assert_eq!(paths.distances[3], Some(4.0));
     }
 
     #[test]
     fn test_dijkstra_negative_weight() {
         let g: Graph<(), f32> = Graph::<(), f32>::from_edges([(0, 1, -1.0)]);
         let result = dijkstra(&g, NodeIndex::new(0));
-        assert!(result.is_err());
+        assert_eq!(
+            result.unwrap_err(),
+            NetOptimError::NegativeWeight {
+                source: NodeIndex::new(0),
+                target: NodeIndex::new(1)
+            }
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_invalid_source() {
+        let g = Graph::<(), u32>::from_edges([(0, 1, 1)]);
+        assert_eq!(
+            dijkstra(&g, NodeIndex::new(2)).unwrap_err(),
+            NetOptimError::InvalidNode(NodeIndex::new(2))
+        );
+        let empty = Graph::<(), u32>::new();
+        assert_eq!(
+            dijkstra(&empty, NodeIndex::new(0)).unwrap_err(),
+            NetOptimError::EmptyGraph
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_path_invalid_target() {
+        let g = Graph::<(), u32>::from_edges([(0, 1, 1)]);
+        let n = NodeIndex::new;
+        assert_eq!(
+            dijkstra_path(&g, n(0), n(7)),
+            Err(NetOptimError::InvalidNode(n(7)))
+        );
+        assert_eq!(
+            astar(&g, n(0), n(7), |_| 0),
+            Err(NetOptimError::InvalidNode(n(7)))
+        );
+        assert_eq!(
+            bidirectional_dijkstra(&g, n(7), n(0)),
+            Err(NetOptimError::InvalidNode(n(7)))
+        );
+        // With both nodes invalid, the source is reported, as by `astar`.
+        assert_eq!(
+            dijkstra_path(&g, n(8), n(7)),
+            Err(NetOptimError::InvalidNode(n(8)))
+        );
+        assert_eq!(
+            astar(&g, n(8), n(7), |_| 0),
+            Err(NetOptimError::InvalidNode(n(8)))
+        );
     }
 
     #[test]
@@ -238,7 +1088,7 @@ mod tests {
         g.extend_with_edges([(0, 1, 2.0), (1, 2, 3.0)]);
 
         let path = dijkstra_path(&g, a, c);
-        assert_eq!(path, Some(vec![a, b, c]));
+        assert_eq!(path, Ok(vec![a, b, c]));
     }
 
     #[test]
@@ -250,7 +1100,7 @@ mod tests {
         g.add_edge(a, b, 1.0);
 
         let path = dijkstra_path(&g, a, c);
-        assert_eq!(path, None);
+        assert_eq!(path, Err(NetOptimError::NoPath));
     }
 
     #[test]
@@ -264,9 +1114,9 @@ mod tests {
         let result = dijkstra(&g, a);
         assert!(result.is_ok());
         let paths = result.unwrap();
-        assert_eq!(paths.distances[a.index()], 0.0);
-        assert_eq!(paths.distances[b.index()], 1.0);
-        assert!(paths.distances[c.index()].is_infinite());
+        assert_eq!(paths.distances[a.index()], Some(0.0));
+        assert_eq!(paths.distances[b.index()], Some(1.0));
+        assert!(paths.distances[c.index()].is_none());
     }
 
     #[test]
@@ -277,7 +1127,7 @@ mod tests {
         let result = dijkstra(&g, a);
         assert!(result.is_ok());
         let paths = result.unwrap();
-        assert_eq!(paths.distances[a.index()], 0.0);
+        assert_eq!(paths.distances[a.index()], Some(0.0));
     }
 
     #[test]
@@ -286,6 +1136,573 @@ mod tests {
         let a = g.add_node(());
 
         let path = dijkstra_path(&g, a, a);
-        assert_eq!(path, Some(vec![a]));
+        assert_eq!(path, Ok(vec![a]));
+    }
+
+    #[test]
+    fn test_dijkstra_integer_weights() {
+        let g = Graph::<(), u64>::from_edges([(0, 1, 7), (0, 2, 9), (1, 2, 1), (2, 3, 2)]);
+        let paths = dijkstra(&g, NodeIndex::new(0)).unwrap();
+        assert_eq!(paths.distances, vec![Some(0), Some(7), Some(8), Some(10)]);
+        assert_eq!(
+            dijkstra_path(&g, NodeIndex::new(0), NodeIndex::new(3)),
+            Ok(vec![
+                NodeIndex::new(0),
+                NodeIndex::new(1),
+                NodeIndex::new(2),
+                NodeIndex::new(3)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_overflow_is_an_error() {
+        // Node 2 is at 300, past the range of u8, not unreachable.
+        let g = Graph::<(), u8>::from_edges([(0, 1, 200), (1, 2, 100)]);
+        let (a, c) = (NodeIndex::new(0), NodeIndex::new(2));
+        let is_overflow = |err| matches!(err, NetOptimError::AlgorithmError(_));
+        assert!(is_overflow(dijkstra(&g, a).unwrap_err()));
+        assert!(is_overflow(dijkstra_path(&g, a, c).unwrap_err()));
+        assert!(is_overflow(astar(&g, a, c, |_| 0).unwrap_err()));
+        assert!(is_overflow(bidirectional_dijkstra(&g, a, c).unwrap_err()));
+
+        // An overflowing sum is skipped when a shorter path reaches the node later:
+        // 200 + 100 overflows before node 2 is reached at 220 through node 3.
+        let g = Graph::<(), u8>::from_edges([(0, 1, 200), (1, 2, 100), (0, 3, 210), (3, 2, 10)]);
+        let paths = dijkstra(&g, a).unwrap();
+        assert_eq!(
+            paths.distances,
+            vec![Some(0), Some(200), Some(220), Some(210)]
+        );
+        assert_eq!(
+            dijkstra_path(&g, a, c).unwrap(),
+            vec![a, NodeIndex::new(3), c]
+        );
+        assert_eq!(astar(&g, a, c, |_| 0).unwrap().0, 220);
+        let path = bidirectional_dijkstra(&g, a, c).unwrap();
+        assert_eq!(path, vec![a, NodeIndex::new(3), c]);
+    }
+
+    #[test]
+    fn test_dijkstra_rational_weights() {
+        use num::rational::Ratio;
+
+        let r = |n, d| Ratio::new(n, d);
+        let g = Graph::<(), Ratio<i64>>::from_edges([
+            (0, 1, r(1, 3)),
+            (1, 2, r(1, 3)),
+            (2, 3, r(1, 3)),
+            (0, 3, r(1, 1)),
+        ]);
+        let paths = dijkstra(&g, NodeIndex::new(0)).unwrap();
+        assert_eq!(paths.distances[3], Some(r(1, 1)));
+        assert_eq!(paths.predecessors[3], Some(NodeIndex::new(0)));
+    }
+
+    // A `side` x `side` grid with lengths in 1..=10; node `side * row + col`.
+    fn grid(side: usize) -> Graph<(), u32> {
+        grid_graph(&mut Lcg::new(7), side, |rng| 1 + rng.below(10) as u32)
+    }
+
+    fn manhattan(side: usize, target: NodeIndex) -> impl Fn(NodeIndex) -> u32 {
+        move |v| {
+            let (r, c) = (v.index() / side, v.index() % side);
+            let (tr, tc) = (target.index() / side, target.index() % side);
+            (r.abs_diff(tr) + c.abs_diff(tc)) as u32
+        }
+    }
+
+    #[test]
+    fn test_astar_matches_dijkstra_on_grid() {
+        let side = 12;
+        let g = grid(side);
+        let source = NodeIndex::new(0);
+        let expected = dijkstra(&g, source).unwrap();
+        for t in [1, 13, 77, 143] {
+            let target = NodeIndex::new(t);
+            let (cost, path) = astar(&g, source, target, manhattan(side, target)).unwrap();
+            assert_eq!(Some(cost), expected.distances[t]);
+            assert_eq!(path.first(), Some(&source));
+            assert_eq!(path.last(), Some(&target));
+            let length: u32 = path
+                .windows(2)
+                .map(|w| g[g.find_edge(w[0], w[1]).unwrap()])
+                .sum();
+            assert_eq!(length, cost);
+        }
+    }
+
+    #[test]
+    fn test_astar_zero_heuristic_is_dijkstra() {
+        let g = Graph::<(), f64>::from_edges([(0, 1, 2.0), (0, 2, 5.0), (1, 2, 1.0), (2, 3, 1.0)]);
+        let (cost, path) = astar(&g, NodeIndex::new(0), NodeIndex::new(3), |_| 0.0).unwrap();
+        assert_eq!(cost, 4.0);
+        assert_eq!(
+            path,
+            dijkstra_path(&g, NodeIndex::new(0), NodeIndex::new(3)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_astar_stops_at_target() {
+        // A long chain behind the target must not be explored.
+        let mut edges = vec![(0, 1, 1), (1, 2, 1)];
+        edges.extend((2..1000).map(|i| (i, i + 1, 1)));
+        let g = Graph::<(), u32>::from_edges(edges);
+        let mut scanned = 0;
+        let result = astar(&g, NodeIndex::new(0), NodeIndex::new(2), |_| {
+            scanned += 1;
+            0
+        })
+        .unwrap();
+        assert_eq!(result.0, 2);
+        assert!(scanned < 10);
+    }
+
+    #[test]
+    fn test_astar_source_is_target() {
+        let g = Graph::<(), u32>::from_edges([(0, 1, 1)]);
+        let a = NodeIndex::new(0);
+        assert_eq!(astar(&g, a, a, |_| 0), Ok((0, vec![a])));
+    }
+
+    #[test]
+    fn test_astar_unreachable() {
+        let g = Graph::<(), u32>::from_edges([(0, 1, 1), (2, 1, 1)]);
+        let result = astar(&g, NodeIndex::new(0), NodeIndex::new(2), |_| 0);
+        assert_eq!(result, Err(NetOptimError::NoPath));
+    }
+
+    #[test]
+    fn test_astar_negative_weight() {
+        let g = Graph::<(), i32>::from_edges([(0, 1, -1), (1, 2, 1)]);
+        assert!(matches!(
+            astar(&g, NodeIndex::new(0), NodeIndex::new(2), |_| 0),
+            Err(NetOptimError::NegativeWeight { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "not consistent")]
+    fn test_astar_inconsistent_heuristic_panics_in_debug() {
+        let g = Graph::<(), u32>::from_edges([(0, 1, 1), (1, 2, 1), (0, 2, 5)]);
+        // Overestimates at the source: h(0) = 10 > w(0, 1) + h(1) = 1.
+        let heuristic = |v: NodeIndex| if v.index() == 0 { 10 } else { 0 };
+        let _ = astar(&g, NodeIndex::new(0), NodeIndex::new(2), heuristic);
+    }
+
+    // Length of a path, taking the shortest of any parallel edges.
+    fn path_cost<E: Copy + Ord + std::iter::Sum<E>>(g: &Graph<(), E>, path: &[NodeIndex]) -> E {
+        path.windows(2)
+            .map(|w| {
+                g.edges_connecting(w[0], w[1])
+                    .map(|e| *e.weight())
+                    .min()
+                    .unwrap()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_bidirectional_dijkstra_matches_dijkstra_on_grid() {
+        let side = 12;
+        let g = grid(side);
+        let source = NodeIndex::new(5);
+        let expected = dijkstra(&g, source).unwrap();
+        for t in 0..side * side {
+            let target = NodeIndex::new(t);
+            let path = bidirectional_dijkstra(&g, source, target).unwrap();
+            assert_eq!(path.first(), Some(&source));
+            assert_eq!(path.last(), Some(&target));
+            assert_eq!(Some(path_cost(&g, &path)), expected.distances[t]);
+        }
+    }
+
+    #[test]
+    fn test_bidirectional_dijkstra_agrees_with_dijkstra_path_on_random_graphs() {
+        let mut rng = Lcg::new(0x5851_f42d);
+        for _ in 0..300 {
+            let n = 2 + rng.below(12) as usize;
+            let m = rng.below(40) as usize;
+            let g = random_graph(&mut rng, n, m, false, |rng| rng.below(10));
+            let source = NodeIndex::new(rng.below(n as u64) as usize);
+            let target = NodeIndex::new(rng.below(n as u64) as usize);
+            let path = bidirectional_dijkstra(&g, source, target);
+            let expected = dijkstra_path(&g, source, target);
+            assert_eq!(path.is_ok(), expected.is_ok());
+            if let (Ok(path), Ok(expected)) = (path, expected) {
+                assert_eq!(path.first(), Some(&source));
+                assert_eq!(path.last(), Some(&target));
+                assert_eq!(path_cost(&g, &path), path_cost(&g, &expected));
+            }
+        }
+    }
+
+    #[test]
+    fn test_bidirectional_dijkstra_prefers_shorter_meeting() {
+        // The first meeting (through 1) is not the shortest path (through 2 and 3).
+        let g =
+            Graph::<(), u32>::from_edges([(0, 1, 5), (1, 4, 5), (0, 2, 3), (2, 3, 3), (3, 4, 3)]);
+        let n = NodeIndex::new;
+        assert_eq!(
+            bidirectional_dijkstra(&g, n(0), n(4)),
+            Ok(vec![n(0), n(2), n(3), n(4)])
+        );
+    }
+
+    #[test]
+    fn test_bidirectional_dijkstra_no_path() {
+        let g = Graph::<(), f64>::from_edges([(0, 1, 1.0), (2, 1, 1.0)]);
+        assert_eq!(
+            bidirectional_dijkstra(&g, NodeIndex::new(0), NodeIndex::new(2)),
+            Err(NetOptimError::NoPath)
+        );
+    }
+
+    #[test]
+    fn test_bidirectional_dijkstra_same_node() {
+        let mut g: Graph<(), f64> = Graph::new();
+        let a = g.add_node(());
+        assert_eq!(bidirectional_dijkstra(&g, a, a), Ok(vec![a]));
+    }
+
+    #[test]
+    fn test_bidirectional_dijkstra_negative_weight() {
+        let g = Graph::<(), i32>::from_edges([(0, 1, -1), (1, 2, 1)]);
+        let n = NodeIndex::new;
+        assert_eq!(
+            bidirectional_dijkstra(&g, n(0), n(2)),
+            Err(NetOptimError::NegativeWeight {
+                source: n(0),
+                target: n(1)
+            })
+        );
+
+        // The backward search, ahead after the forward one settles 0, finds 2 -> 3.
+        let g = Graph::<(), i32>::from_edges([(0, 1, 10), (2, 3, -1), (1, 3, 1)]);
+        assert_eq!(
+            bidirectional_dijkstra(&g, n(0), n(3)),
+            Err(NetOptimError::NegativeWeight {
+                source: n(2),
+                target: n(3)
+            })
+        );
+    }
+
+    #[test]
+    fn test_bidirectional_dijkstra_undirected() {
+        let g = petgraph::graph::UnGraph::<(), u32>::from_edges([(0, 1, 1), (2, 1, 1), (2, 3, 4)]);
+        let n = NodeIndex::new;
+        assert_eq!(
+            bidirectional_dijkstra(&g, n(3), n(0)),
+            Ok(vec![n(3), n(2), n(1), n(0)])
+        );
+    }
+
+    #[test]
+    fn test_bidirectional_dijkstra_stable_graph_with_holes() {
+        let mut g = petgraph::stable_graph::StableDiGraph::<(), u32>::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        let d = g.add_node(());
+        g.add_edge(a, c, 1);
+        g.add_edge(c, d, 1);
+        g.remove_node(b);
+        assert_eq!(bidirectional_dijkstra(&g, a, d), Ok(vec![a, c, d]));
+    }
+
+    #[test]
+    fn test_dijkstra_with_options_default_matches_dijkstra() {
+        let g = grid(8);
+        let source = NodeIndex::new(9);
+        let paths = dijkstra_with_options(&g, source, &DijkstraOptions::new()).unwrap();
+        let expected = dijkstra(&g, source).unwrap();
+        assert_eq!(paths.distances, expected.distances);
+        assert_eq!(paths.predecessors, expected.predecessors);
+    }
+
+    #[test]
+    fn test_dijkstra_with_options_radius_returns_settled_region() {
+        let g = grid(10);
+        let source = NodeIndex::new(44);
+        let full = dijkstra(&g, source).unwrap();
+        let radius = 12;
+        let options = DijkstraOptions::new().with_radius(radius);
+        let paths = dijkstra_with_options(&g, source, &options).unwrap();
+        for v in 0..100 {
+            let expected = full.distances[v].filter(|&d| d <= radius);
+            assert_eq!(paths.distances[v], expected);
+            if expected.is_some() {
+                assert_eq!(paths.predecessors[v], full.predecessors[v]);
+            } else {
+                assert_eq!(paths.predecessors[v], None);
+            }
+        }
+        assert!(paths.distances.iter().any(|d| d.is_none()));
+    }
+
+    #[test]
+    fn test_dijkstra_with_options_zero_radius() {
+        let g = Graph::<(), u32>::from_edges([(0, 1, 0), (1, 2, 1)]);
+        let options = DijkstraOptions::new().with_radius(0);
+        let paths = dijkstra_with_options(&g, NodeIndex::new(0), &options).unwrap();
+        assert_eq!(paths.distances, vec![Some(0), Some(0), None]);
+    }
+
+    #[test]
+    fn test_dijkstra_with_options_stops_at_targets() {
+        let g =
+            Graph::<(), u32>::from_edges([(0, 1, 1), (0, 2, 5), (1, 3, 1), (3, 4, 10), (2, 5, 1)]);
+        let n = NodeIndex::new;
+        let options = DijkstraOptions::new().with_targets([n(3), n(1), n(3)]);
+        let paths = dijkstra_with_options(&g, n(0), &options).unwrap();
+        assert_eq!(
+            paths.distances,
+            vec![Some(0), Some(1), None, Some(2), None, None]
+        );
+        assert_eq!(paths.predecessors[3], Some(n(1)));
+    }
+
+    #[test]
+    fn test_dijkstra_with_options_unreachable_target_settles_everything() {
+        let g = Graph::<(), u32>::from_edges([(0, 1, 1), (1, 2, 1), (3, 0, 1)]);
+        let options = DijkstraOptions::new().with_targets([NodeIndex::new(3)]);
+        let paths = dijkstra_with_options(&g, NodeIndex::new(0), &options).unwrap();
+        assert_eq!(paths.distances, vec![Some(0), Some(1), Some(2), None]);
+    }
+
+    #[test]
+    fn test_dijkstra_with_options_no_targets_settles_source() {
+        let g = Graph::<(), u32>::from_edges([(0, 1, 1), (1, 2, 1)]);
+        let options = DijkstraOptions::new().with_targets([]);
+        let paths = dijkstra_with_options(&g, NodeIndex::new(1), &options).unwrap();
+        assert_eq!(paths.distances, vec![None, Some(0), None]);
+        assert_eq!(paths.predecessors, vec![None; 3]);
+    }
+
+    #[test]
+    fn test_dijkstra_with_options_invalid_target() {
+        let g = Graph::<(), u32>::from_edges([(0, 1, 1)]);
+        let n = NodeIndex::new;
+        let options = DijkstraOptions::new().with_targets([n(1), n(99)]);
+        assert_eq!(
+            dijkstra_with_options(&g, n(0), &options).unwrap_err(),
+            NetOptimError::InvalidNode(n(99))
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_with_options_targets_and_radius() {
+        let g = Graph::<(), f64>::from_edges([(0, 1, 1.0), (1, 2, 1.0), (2, 3, 1.0)]);
+        let n = NodeIndex::new;
+        // The radius is hit before the target.
+        let options = DijkstraOptions::new().with_targets([n(3)]).with_radius(1.5);
+        let paths = dijkstra_with_options(&g, n(0), &options).unwrap();
+        assert_eq!(paths.distances, vec![Some(0.0), Some(1.0), None, None]);
+    }
+
+    #[test]
+    fn test_dijkstra_with_potentials_matches_bellman_ford() {
+        let mut rng = Lcg::new(0x9e37_79b9);
+        for _ in 0..100 {
+            // Forward arcs only, so negative weights cannot form a cycle.
+            let n = 2 + rng.below(10) as usize;
+            let m = rng.below(30) as usize;
+            let g = random_graph(&mut rng, n, m, true, |rng| rng.below(20) as i64 - 8);
+            let potentials: Vec<i64> = crate::bellman_ford_virtual_source(&g)
+                .unwrap()
+                .distances
+                .into_iter()
+                .map(Option::unwrap)
+                .collect();
+            for s in g.node_indices() {
+                let paths = dijkstra_with_potentials(&g, s, &potentials).unwrap();
+                let expected = crate::bellman_ford(&g, s).unwrap();
+                assert_eq!(paths.distances, expected.distances);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dijkstra_with_potentials_from_neg_cycle_finder() {
+        let g = Graph::<(), i32>::from_edges([(0, 1, 3), (1, 2, -2), (2, 3, -1), (0, 3, 1)]);
+        let mut ncf = crate::neg_cycle::NegCycleFinder::new(&g);
+        let mut dist = vec![0; 4];
+        assert!(ncf.howard(&mut dist, |e| *e.weight()).is_none());
+        let paths = dijkstra_with_potentials(&g, NodeIndex::new(0), &dist).unwrap();
+        assert_eq!(paths.distances, vec![Some(0), Some(3), Some(1), Some(0)]);
+        assert_eq!(paths.predecessors[3], Some(NodeIndex::new(2)));
+    }
+
+    #[test]
+    fn test_dijkstra_with_potentials_zero_is_dijkstra() {
+        let g = grid(6);
+        let source = NodeIndex::new(7);
+        let paths = dijkstra_with_potentials(&g, source, &[0; 36]).unwrap();
+        let expected = dijkstra(&g, source).unwrap();
+        assert_eq!(paths.distances, expected.distances);
+        assert_eq!(paths.predecessors, expected.predecessors);
+    }
+
+    #[test]
+    fn test_dijkstra_with_potentials_rejects_infeasible_potentials() {
+        let g = Graph::<(), f64>::from_edges([(0, 1, 1.0), (1, 2, 2.0)]);
+        let n = NodeIndex::new;
+        // Positive weights, but p(1) = 5 makes the reduced cost of 0 -> 1 negative.
+        let err = dijkstra_with_potentials(&g, n(0), &[0.0, 5.0, 0.0]).unwrap_err();
+        assert_eq!(
+            err,
+            NetOptimError::NegativeReducedCost {
+                source: n(0),
+                target: n(1)
+            }
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_with_potentials_unsigned() {
+        let g = Graph::<(), u32>::from_edges([(0, 1, 1), (1, 2, 2)]);
+        let n = NodeIndex::new;
+        let paths = dijkstra_with_potentials(&g, n(0), &[3, 2, 0]).unwrap();
+        assert_eq!(paths.distances, vec![Some(0), Some(1), Some(3)]);
+
+        // The reduced cost 1 + 0 - 5 of 0 -> 1 has no unsigned value.
+        assert_eq!(
+            dijkstra_with_potentials(&g, n(0), &[0, 5, 0]).unwrap_err(),
+            NetOptimError::NegativeReducedCost {
+                source: n(0),
+                target: n(1)
+            }
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_with_potentials_overflow() {
+        let g = Graph::<(), i32>::from_edges([(0, 1, 1)]);
+        let n = NodeIndex::new;
+        // Feasible, with reduced cost 2, but 1 + p(0) overflows.
+        assert!(matches!(
+            dijkstra_with_potentials(&g, n(0), &[i32::MAX, i32::MAX - 1]),
+            Err(NetOptimError::AlgorithmError(_))
+        ));
+
+        // Reduced distance i32::MAX to node 2, to which adding p(2) overflows: the
+        // reweighting is undone by subtracting p(0) first.
+        let half = 1 << 30;
+        let g = Graph::<(), i32>::from_edges([(0, 1, half - 1), (1, 2, half)]);
+        let paths = dijkstra_with_potentials(&g, n(0), &[half, 0, half]).unwrap();
+        assert_eq!(paths.distances[2], Some(i32::MAX));
+        // One more and the distance itself is out of range.
+        let g = Graph::<(), i32>::from_edges([(0, 1, half - 1), (1, 2, half + 1)]);
+        assert!(matches!(
+            dijkstra_with_potentials(&g, n(0), &[half, 0, half + 1]),
+            Err(NetOptimError::AlgorithmError(_))
+        ));
+    }
+
+    #[test]
+    fn test_dijkstra_with_potentials_invalid_input() {
+        let g = Graph::<(), i32>::from_edges([(0, 1, 1), (1, 2, 1)]);
+        let n = NodeIndex::new;
+        assert!(matches!(
+            dijkstra_with_potentials(&g, n(0), &[0, 0]),
+            Err(NetOptimError::AlgorithmError(_))
+        ));
+        assert_eq!(
+            dijkstra_with_potentials(&g, n(3), &[0, 0, 0]).unwrap_err(),
+            NetOptimError::InvalidNode(n(3))
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_with_queue_matches_dijkstra() {
+        use crate::monotone_queue::{BucketQueue, RadixHeap};
+
+        let mut rng = Lcg::new(0x7f4a_7c15);
+        for max_weight in [1, 10, 1000] {
+            for _ in 0..50 {
+                let n = 2 + rng.below(15) as usize;
+                let m = rng.below(50) as usize;
+                let g = random_graph(&mut rng, n, m, false, |rng| rng.below(max_weight + 1));
+                for s in g.node_indices() {
+                    let expected = dijkstra(&g, s).unwrap().distances;
+                    let buckets = dijkstra_with_queue(&g, s, BucketQueue::new()).unwrap();
+                    assert_eq!(buckets.distances, expected);
+                    let radix = dijkstra_with_queue(&g, s, RadixHeap::new()).unwrap();
+                    assert_eq!(radix.distances, expected);
+                    for v in g.node_indices() {
+                        // Each predecessor is the last node of a shortest path.
+                        if let Some(p) = radix.predecessors[v.index()] {
+                            let w = g.edges_connecting(p, v).map(|e| *e.weight()).min();
+                            let via = expected[p.index()].zip(w).map(|(d, w)| d + w);
+                            assert_eq!(via, expected[v.index()]);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dijkstra_with_queue_grid() {
+        let g = grid(10);
+        let source = NodeIndex::new(44);
+        let expected = dijkstra(&g, source).unwrap().distances;
+        let buckets = crate::monotone_queue::BucketQueue::new();
+        assert_eq!(
+            dijkstra_with_queue(&g, source, buckets).unwrap().distances,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_with_queue_overflow_is_an_error() {
+        let g = Graph::<(), u8>::from_edges([(0, 1, 200), (1, 2, 100)]);
+        let queue = crate::monotone_queue::RadixHeap::new();
+        assert!(matches!(
+            dijkstra_with_queue(&g, NodeIndex::new(0), queue),
+            Err(NetOptimError::AlgorithmError(_))
+        ));
+
+        let g = Graph::<(), u8>::from_edges([(0, 1, 200), (1, 2, 100), (0, 3, 210), (3, 2, 10)]);
+        let queue = crate::monotone_queue::RadixHeap::new();
+        let paths = dijkstra_with_queue(&g, NodeIndex::new(0), queue).unwrap();
+        assert_eq!(
+            paths.distances,
+            vec![Some(0), Some(200), Some(220), Some(210)]
+        );
+    }
+
+    #[test]
+    fn test_dijkstra_with_queue_key_out_of_range() {
+        let big = u128::from(u64::MAX) + 1;
+        let g = Graph::<(), u128>::from_edges([(0, 1, 1), (0, 2, big)]);
+        let queue = crate::monotone_queue::RadixHeap::new();
+        assert!(matches!(
+            dijkstra_with_queue(&g, NodeIndex::new(0), queue),
+            Err(NetOptimError::AlgorithmError(_))
+        ));
+
+        // Up to u64::MAX, wide weights are fine.
+        let g = Graph::<(), u128>::from_edges([(0, 1, big - 1)]);
+        let queue = crate::monotone_queue::RadixHeap::new();
+        let paths = dijkstra_with_queue(&g, NodeIndex::new(0), queue).unwrap();
+        assert_eq!(paths.distances, vec![Some(0), Some(big - 1)]);
+    }
+
+    #[test]
+    fn test_dijkstra_with_queue_negative_weight() {
+        let g = Graph::<(), i32>::from_edges([(0, 1, 1), (1, 2, -1)]);
+        let n = NodeIndex::new;
+        let queue = crate::monotone_queue::BucketQueue::new();
+        assert_eq!(
+            dijkstra_with_queue(&g, n(0), queue).unwrap_err(),
+            NetOptimError::NegativeWeight {
+                source: n(1),
+                target: n(2)
+            }
+        );
     }
 }
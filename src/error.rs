@@ -3,39 +3,66 @@
 use std::fmt;
 
 /// Error types for network optimization algorithms
+///
+/// `N` identifies nodes: the graph's `NodeId` for the graph algorithms, so that an
+/// error points at the offending nodes, and a plain index elsewhere.
 #[derive(Debug, Clone, PartialEq)]
-pub enum NetOptimError {
-    /// Negative edge weight found where non-negative weights are required
-    NegativeWeight,
-    /// Negative cycle detected in the graph
-    NegativeCycle,
+pub enum NetOptimError<N = usize> {
+    /// Negative edge weight found where non-negative weights are required, on the
+    /// edge from `source` to `target`
+    NegativeWeight { source: N, target: N },
+    /// Node potentials are not feasible: the edge from `source` to `target` has a
+    /// negative reduced cost
+    NegativeReducedCost { source: N, target: N },
+    /// Extending a path by the edge from `source` to `target` would improve its value,
+    /// which a path algebra must never allow
+    NonMonotoneEdge { source: N, target: N },
+    /// Negative cycle detected in the graph, with its nodes in traversal order
+    NegativeCycle(Vec<N>),
     /// No path exists between nodes
     NoPath,
     /// Invalid node index
-    InvalidNode,
+    InvalidNode(N),
     /// Graph is empty
     EmptyGraph,
     /// Algorithm-specific error with message
     AlgorithmError(String),
 }
 
-impl fmt::Display for NetOptimError {
+impl<N: fmt::Debug> fmt::Display for NetOptimError<N> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            NetOptimError::NegativeWeight => {
+            NetOptimError::NegativeWeight { source, target } => {
                 write!(
                     f,
-                    "Negative edge weight found where non-negative weights are required"
+                    "Negative edge weight found where non-negative weights are required \
+                     (edge {:?} -> {:?})",
+                    source, target
                 )
             }
-            NetOptimError::NegativeCycle => {
-                write!(f, "Negative cycle detected in the graph")
+            NetOptimError::NegativeReducedCost { source, target } => {
+                write!(
+                    f,
+                    "Infeasible potentials: negative reduced cost on edge {:?} -> {:?}",
+                    source, target
+                )
+            }
+            NetOptimError::NonMonotoneEdge { source, target } => {
+                write!(
+                    f,
+                    "Edge improves the paths it extends, but the path algebra must be monotone \
+                     (edge {:?} -> {:?})",
+                    source, target
+                )
+            }
+            NetOptimError::NegativeCycle(cycle) => {
+                write!(f, "Negative cycle detected in the graph: {:?}", cycle)
             }
             NetOptimError::NoPath => {
                 write!(f, "No path exists between the specified nodes")
             }
-            NetOptimError::InvalidNode => {
-                write!(f, "Invalid node index provided")
+            NetOptimError::InvalidNode(node) => {
+                write!(f, "Invalid node index provided: {:?}", node)
             }
             NetOptimError::EmptyGraph => {
                 write!(f, "Graph is empty")
@@ -47,41 +74,90 @@ impl fmt::Display for NetOptimError {
     }
 }
 
-impl std::error::Error for NetOptimError {}
+impl<N: fmt::Debug> std::error::Error for NetOptimError<N> {}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use petgraph::graph::NodeIndex;
 
     #[test]
     fn test_error_display() {
         assert_eq!(
-            format!("{}", NetOptimError::NegativeWeight),
-            "Negative edge weight found where non-negative weights are required"
+            format!(
+                "{}",
+                NetOptimError::NegativeWeight {
+                    source: 1,
+                    target: 2
+                }
+            ),
+            "Negative edge weight found where non-negative weights are required (edge 1 -> 2)"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                NetOptimError::NegativeReducedCost {
+                    source: 4,
+                    target: 0
+                }
+            ),
+            "Infeasible potentials: negative reduced cost on edge 4 -> 0"
         );
         assert_eq!(
-            format!("{}", NetOptimError::NegativeCycle),
-            "Negative cycle detected in the graph"
+            format!(
+                "{}",
+                NetOptimError::NonMonotoneEdge {
+                    source: 2,
+                    target: 3
+                }
+            ),
+            "Edge improves the paths it extends, but the path algebra must be monotone \
+             (edge 2 -> 3)"
         );
         assert_eq!(
-            format!("{}", NetOptimError::NoPath),
+            format!("{}", NetOptimError::NegativeCycle(vec![0, 3])),
+            "Negative cycle detected in the graph: [0, 3]"
+        );
+        assert_eq!(
+            format!("{}", NetOptimError::<usize>::NoPath),
             "No path exists between the specified nodes"
         );
         assert_eq!(
-            format!("{}", NetOptimError::InvalidNode),
-            "Invalid node index provided"
+            format!("{}", NetOptimError::InvalidNode(7)),
+            "Invalid node index provided: 7"
         );
-        assert_eq!(format!("{}", NetOptimError::EmptyGraph), "Graph is empty");
         assert_eq!(
-            format!("{}", NetOptimError::AlgorithmError("test".to_string())),
+            format!("{}", NetOptimError::<usize>::EmptyGraph),
+            "Graph is empty"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                NetOptimError::<usize>::AlgorithmError("test".to_string())
+            ),
             "Algorithm error: test"
         );
     }
 
     #[test]
     fn test_error_equality() {
-        assert_eq!(NetOptimError::NegativeWeight, NetOptimError::NegativeWeight);
-        assert_eq!(NetOptimError::NegativeCycle, NetOptimError::NegativeCycle);
-        assert_ne!(NetOptimError::NegativeWeight, NetOptimError::NegativeCycle);
+        let negative_weight = NetOptimError::NegativeWeight {
+            source: 0,
+            target: 1,
+        };
+        assert_eq!(negative_weight, negative_weight.clone());
+        assert_eq!(
+            NetOptimError::NegativeCycle(vec![1, 2]),
+            NetOptimError::NegativeCycle(vec![1, 2])
+        );
+        assert_ne!(negative_weight, NetOptimError::NegativeCycle(vec![0, 1]));
+    }
+
+    #[test]
+    fn test_error_with_graph_nodes() {
+        let err = NetOptimError::InvalidNode(NodeIndex::<u32>::new(4));
+        assert_eq!(err.to_string(), "Invalid node index provided: NodeIndex(4)");
+        let boxed: Box<dyn std::error::Error> = Box::new(err);
+        assert!(boxed.to_string().contains("NodeIndex(4)"));
     }
 }
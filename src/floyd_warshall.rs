@@ -0,0 +1,260 @@
+//! Floyd-Warshall all-pairs shortest paths.
+//!
+//! O(|V|³) time and O(|V|²) memory regardless of the number of edges, so on dense,
+//! nearly complete graphs it beats repeated single-source runs. Negative cycles do
+//! not abort the computation: every pair whose walks can pass through one is
+//! reported as affected instead.
+
+use petgraph::visit::{EdgeRef, IntoEdges, IntoNodeIdentifiers, NodeIndexable};
+
+use crate::weight::Weight;
+use crate::AllPairsPaths;
+
+/// Result of [`floyd_warshall`].
+#[derive(Debug, Clone)]
+pub struct FloydWarshallPaths<NodeId, EdgeWeight> {
+    /// Distances and predecessors. Pairs affected by a negative cycle have no
+    /// shortest path, and are reported as `None` in both matrices.
+    pub paths: AllPairsPaths<NodeId, EdgeWeight>,
+    /// `affected[s][t]` is `true` if a walk from `s` to `t` can pass through a negative
+    /// cycle, i.e. the distance is unbounded below.
+    pub affected: Vec<Vec<bool>>,
+}
+
+impl<NodeId, EdgeWeight> FloydWarshallPaths<NodeId, EdgeWeight> {
+    /// Whether the graph contains a negative cycle.
+    pub fn has_negative_cycle(&self) -> bool {
+        self.affected.iter().any(|row| row.iter().any(|&a| a))
+    }
+}
+
+/// \[Generic\] Compute shortest paths between all pairs of nodes using the
+/// [Floyd-Warshall algorithm][fw].
+///
+/// Negative edge costs are permitted. Pairs affected by negative cycles are marked in
+/// [`FloydWarshallPaths::affected`]; all other pairs get exact distances, and paths can
+/// be rebuilt with [`AllPairsPaths::path`].
+///
+/// [fw]: https://en.wikipedia.org/wiki/Floyd%E2%80%93Warshall_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::prelude::*;
+/// use netoptim_rs::floyd_warshall::floyd_warshall;
+///
+/// let g = Graph::<(), i32>::from_edges(&[(0, 1, 3), (1, 2, -2), (0, 2, 4), (2, 3, 1)]);
+/// let result = floyd_warshall(&g);
+/// assert!(!result.has_negative_cycle());
+/// assert_eq!(result.paths.distances[0][3], Some(2));
+/// let path = result.paths.path(&g, NodeIndex::new(0), NodeIndex::new(3));
+/// assert_eq!(path, Some(vec![0.into(), 1.into(), 2.into(), 3.into()]));
+///
+/// // 1 -> 2 -> 1 is a negative cycle reachable from 0, but not from 3.
+/// let g = Graph::<(), i32>::from_edges(&[(0, 1, 1), (1, 2, -2), (2, 1, 1), (3, 0, 1)]);
+/// let result = floyd_warshall(&g);
+/// assert!(result.affected[0][2]);
+/// assert!(!result.affected[3][3]);
+/// assert_eq!(result.paths.distances[3][0], Some(1));
+/// ```
+pub fn floyd_warshall<G>(g: G) -> FloydWarshallPaths<G::NodeId, G::EdgeWeight>
+where
+    G: IntoNodeIdentifiers + IntoEdges + NodeIndexable,
+    G::EdgeWeight: Weight,
+{
+    let n = g.node_bound();
+    let ix = |i| g.to_index(i);
+
+    // Step 1: initialize with the direct edges
+    let mut distances: Vec<Vec<Option<G::EdgeWeight>>> = vec![vec![None; n]; n];
+    let mut predecessors = vec![vec![None; n]; n];
+    for u in g.node_identifiers() {
+        let ux = ix(u);
+        distances[ux][ux] = Some(G::EdgeWeight::zero());
+        for edge in g.edges(u) {
+            let vx = ix(edge.target());
+            let weight = edge.weight();
+            if distances[ux][vx].as_ref().is_none_or(|d| weight < d) {
+                distances[ux][vx] = Some(weight.clone());
+                predecessors[ux][vx] = Some(u);
+            }
+        }
+    }
+
+    // Step 2: allow each node in turn as an intermediate node
+    for k in 0..n {
+        let via_k = distances[k].clone();
+        let via_k_pred = predecessors[k].clone();
+        for i in 0..n {
+            let Some(to_k) = distances[i][k].clone() else {
+                continue;
+            };
+            for j in 0..n {
+                let Some(from_k) = &via_k[j] else {
+                    continue;
+                };
+                let Some(candidate) = to_k.checked_add(from_k) else {
+                    continue;
+                };
+                if distances[i][j].as_ref().is_none_or(|d| candidate < *d) {
+                    distances[i][j] = Some(candidate);
+                    predecessors[i][j] = via_k_pred[j];
+                }
+            }
+        }
+    }
+
+    // Step 3: a node on a negative cycle has a negative distance to itself, and every
+    // pair connected through it is unbounded below.
+    let zero = G::EdgeWeight::zero();
+    let on_cycle: Vec<usize> = (0..n)
+        .filter(|&k| distances[k][k].as_ref().is_some_and(|d| *d < zero))
+        .collect();
+    let mut affected = vec![vec![false; n]; n];
+    for &k in &on_cycle {
+        for i in 0..n {
+            if distances[i][k].is_none() {
+                continue;
+            }
+            for j in 0..n {
+                if distances[k][j].is_some() {
+                    affected[i][j] = true;
+                }
+            }
+        }
+    }
+    for i in 0..n {
+        for j in 0..n {
+            if affected[i][j] {
+                distances[i][j] = None;
+                predecessors[i][j] = None;
+            }
+        }
+    }
+
+    FloydWarshallPaths {
+        paths: AllPairsPaths {
+            distances,
+            predecessors,
+        },
+        affected,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bellman_ford;
+    use crate::johnson::johnson;
+    use petgraph::prelude::*;
+    use petgraph::Graph;
+
+    #[test]
+    fn test_floyd_warshall_matches_johnson() {
+        let graph = Graph::<(), i64, Directed>::from_edges([
+            (0, 1, 6),
+            (0, 2, 7),
+            (1, 2, 8),
+            (1, 3, 5),
+            (1, 4, -4),
+            (2, 3, -3),
+            (2, 4, 9),
+            (3, 1, -2),
+            (4, 0, 2),
+            (4, 3, 7),
+        ]);
+        let result = floyd_warshall(&graph);
+        assert!(!result.has_negative_cycle());
+        let expected = johnson(&graph).unwrap();
+        assert_eq!(result.paths.distances, expected.distances);
+    }
+
+    #[test]
+    fn test_floyd_warshall_paths_compatible_with_bellman_ford() {
+        let graph = Graph::<(), f64, Directed>::from_edges([
+            (0, 1, 2.0),
+            (1, 2, -1.0),
+            (0, 2, 3.0),
+            (2, 3, 0.5),
+        ]);
+        let result = floyd_warshall(&graph);
+        let from_zero = result.paths.paths_from(0);
+        let expected = bellman_ford(&graph, NodeIndex::new(0)).unwrap();
+        assert_eq!(from_zero.distances, expected.distances);
+        assert_eq!(from_zero.predecessors, expected.predecessors);
+    }
+
+    #[test]
+    fn test_floyd_warshall_path_reconstruction() {
+        let graph = Graph::<(), u32, Directed>::from_edges([
+            (0, 1, 1),
+            (1, 2, 1),
+            (2, 3, 1),
+            (0, 3, 5),
+            (3, 0, 1),
+        ]);
+        let result = floyd_warshall(&graph);
+        let n = |i| NodeIndex::new(i);
+        assert_eq!(
+            result.paths.path(&graph, n(0), n(3)),
+            Some(vec![n(0), n(1), n(2), n(3)])
+        );
+        assert_eq!(
+            result.paths.path(&graph, n(2), n(1)),
+            Some(vec![n(2), n(3), n(0), n(1)])
+        );
+        assert_eq!(result.paths.path(&graph, n(1), n(1)), Some(vec![n(1)]));
+    }
+
+    #[test]
+    fn test_floyd_warshall_unreachable() {
+        let graph = Graph::<(), i32, Directed>::from_edges([(0, 1, 1), (2, 1, 1)]);
+        let result = floyd_warshall(&graph);
+        assert_eq!(result.paths.distances[0], vec![Some(0), Some(1), None]);
+        assert_eq!(result.paths.path(&graph, 0.into(), 2.into()), None);
+    }
+
+    #[test]
+    fn test_floyd_warshall_affected_pairs() {
+        // 1 -> 2 -> 1 is a negative cycle; 0 reaches it, 3 only leaves from it.
+        let graph = Graph::<(), i32, Directed>::from_edges([
+            (0, 1, 1),
+            (1, 2, -2),
+            (2, 1, 1),
+            (2, 3, 1),
+            (4, 0, 1),
+        ]);
+        let result = floyd_warshall(&graph);
+        assert!(result.has_negative_cycle());
+        for (s, t) in [(0, 1), (0, 3), (1, 1), (2, 3), (4, 3)] {
+            assert!(result.affected[s][t], "({s}, {t}) should be affected");
+            assert_eq!(result.paths.distances[s][t], None);
+        }
+        for (s, t) in [(0, 0), (3, 3), (4, 0), (3, 1)] {
+            assert!(!result.affected[s][t], "({s}, {t}) should not be affected");
+        }
+        assert_eq!(result.paths.distances[4][0], Some(1));
+    }
+
+    #[test]
+    fn test_floyd_warshall_negative_self_loop() {
+        let graph = Graph::<(), i32, Directed>::from_edges([(0, 1, 1), (1, 1, -1)]);
+        let result = floyd_warshall(&graph);
+        assert!(result.affected[0][1]);
+        assert!(result.affected[1][1]);
+        assert!(!result.affected[0][0]);
+    }
+
+    #[test]
+    fn test_floyd_warshall_stable_graph_with_holes() {
+        let mut graph = StableDiGraph::<(), i32>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        let c = graph.add_node(());
+        graph.add_edge(a, c, -3);
+        graph.remove_node(b);
+        let result = floyd_warshall(&graph);
+        assert_eq!(result.paths.distances[a.index()][c.index()], Some(-3));
+        assert_eq!(result.paths.path(&graph, a, c), Some(vec![a, c]));
+    }
+}
@@ -0,0 +1,380 @@
+//! Goldberg-Radzik shortest path algorithm.
+//!
+//! Each pass takes the labeled nodes that still have an improving arc, searches the
+//! admissible subgraph (arcs of non-positive reduced cost `d(u) + w(u, v) - d(v)`)
+//! from them, and scans the reached nodes in topological order. On graphs that are
+//! mostly acyclic a distance improvement then travels down a whole chain in a single
+//! pass, where [`bellman_ford`](crate::bellman_ford) needs one pass per arc.
+//!
+//! A cycle of admissible arcs containing a negative one is a negative cycle. Zero
+//! cycles of admissible arcs are harmless and only relax the topological order.
+
+use petgraph::visit::{EdgeRef, IntoEdges, IntoNodeIdentifiers, NodeCount, NodeIndexable};
+
+use crate::error::NetOptimError;
+use crate::weight::Weight;
+use crate::{relaxed_distance, validate_node, Overflowed, Paths};
+
+/// \[Generic\] Compute shortest paths from node `source` to all other using the
+/// [Goldberg-Radzik algorithm][gr].
+///
+/// Negative arcs are allowed. Returns the same [`Paths`] as
+/// [`bellman_ford`](crate::bellman_ford), or the same errors:
+/// [`NetOptimError::NegativeCycle`] with the nodes of a negative cycle reachable from
+/// `source`. The worst case is O(|V|·|E|).
+///
+/// [gr]: https://doi.org/10.1016/0893-9659(93)90022-F
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::prelude::*;
+/// use netoptim_rs::{goldberg_radzik, NetOptimError};
+///
+/// let g = Graph::<(), i32>::from_edges(&[(0, 1, 2), (1, 2, -3), (0, 2, 1), (2, 3, 4)]);
+/// let paths = goldberg_radzik(&g, NodeIndex::new(0)).unwrap();
+/// assert_eq!(paths.distances, vec![Some(0), Some(2), Some(-1), Some(3)]);
+///
+/// let g = Graph::<(), i32>::from_edges(&[(0, 1, 1), (1, 2, -2), (2, 1, 1)]);
+/// let err = goldberg_radzik(&g, NodeIndex::new(0)).unwrap_err();
+/// assert!(matches!(err, NetOptimError::NegativeCycle(cycle) if cycle.len() == 2));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn goldberg_radzik<G>(
+    g: G,
+    source: G::NodeId,
+) -> Result<Paths<G::NodeId, G::EdgeWeight>, NetOptimError<G::NodeId>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable,
+    G::EdgeWeight: Weight,
+{
+    validate_node(g, source)?;
+    let n = g.node_bound();
+    let ix = |i| g.to_index(i);
+
+    let mut distances = vec![None; n];
+    let mut predecessors = vec![None; n];
+    let mut labeled = vec![false; n];
+    // Pass in which a node was last reached by the search.
+    let mut seen = vec![0; n];
+    // Position of a node on the search stack, or `usize::MAX` if it is not on it.
+    let mut stack_pos = vec![usize::MAX; n];
+
+    distances[ix(source)] = Some(G::EdgeWeight::zero());
+    labeled[ix(source)] = true;
+
+    let mut labeled_nodes = vec![source];
+    let mut order = Vec::new();
+    // Search stack: node, its unexplored arcs, and the number of negative arcs on the
+    // search path from the root to the node.
+    let mut stack: Vec<(G::NodeId, G::Edges, usize)> = Vec::new();
+    let mut pass = 0;
+    let mut overflowed = Overflowed::default();
+
+    while !labeled_nodes.is_empty() {
+        pass += 1;
+
+        // Step 1: order the admissible subgraph reachable from the labeled nodes that
+        // still have an improving arc (reverse postorder of a depth-first search).
+        order.clear();
+        for root in std::mem::take(&mut labeled_nodes) {
+            let rx = ix(root);
+            if !labeled[rx] || seen[rx] == pass {
+                continue;
+            }
+            labeled[rx] = false;
+            let mut improvable = false;
+            for edge in g.edges(root) {
+                let vx = ix(edge.target());
+                let relaxed = relaxed_distance(
+                    &distances[rx],
+                    &distances[vx],
+                    edge.weight(),
+                    (rx, vx),
+                    &mut overflowed,
+                );
+                if relaxed?.is_some() {
+                    improvable = true;
+                    break;
+                }
+            }
+            if !improvable {
+                continue;
+            }
+
+            seen[rx] = pass;
+            stack_pos[rx] = 0;
+            stack.push((root, g.edges(root), 0));
+            while let Some(top) = stack.last_mut() {
+                let (u, negatives) = (top.0, top.2);
+                let Some(edge) = top.1.next() else {
+                    stack_pos[ix(u)] = usize::MAX;
+                    order.push(u);
+                    stack.pop();
+                    continue;
+                };
+                let v = edge.target();
+                let vx = ix(v);
+                let ux = ix(u);
+                let arc = admissible(
+                    &distances[ux],
+                    &distances[vx],
+                    edge.weight(),
+                    (ux, vx),
+                    &mut overflowed,
+                );
+                let Some(negative) = arc? else {
+                    continue;
+                };
+                let negatives = negatives + usize::from(negative);
+                if stack_pos[vx] != usize::MAX {
+                    // The arc closes a cycle of admissible arcs: the stack from `v` up.
+                    if negatives > stack[stack_pos[vx]].2 {
+                        let cycle = stack[stack_pos[vx]..].iter().map(|entry| entry.0);
+                        return Err(NetOptimError::NegativeCycle(cycle.collect()));
+                    }
+                    continue;
+                }
+                if seen[vx] == pass {
+                    continue;
+                }
+                seen[vx] = pass;
+                stack_pos[vx] = stack.len();
+                stack.push((v, g.edges(v), negatives));
+            }
+        }
+
+        // Step 2: scan the reached nodes in topological order.
+        for &u in order.iter().rev() {
+            let ux = ix(u);
+            labeled[ux] = false;
+            for edge in g.edges(u) {
+                let v = edge.target();
+                let vx = ix(v);
+                let relaxed = relaxed_distance(
+                    &distances[ux],
+                    &distances[vx],
+                    edge.weight(),
+                    (ux, vx),
+                    &mut overflowed,
+                );
+                if let Some(d) = relaxed? {
+                    distances[vx] = Some(d);
+                    predecessors[vx] = Some(u);
+                    if !labeled[vx] {
+                        labeled[vx] = true;
+                        labeled_nodes.push(v);
+                    }
+                }
+            }
+        }
+
+        // Without negative cycles |V| passes suffice (up to zero cycles); past that,
+        // check the predecessor graph, whose cycles are always negative.
+        if pass >= g.node_count() {
+            if let Some(cycle) = predecessor_cycle(g, &predecessors) {
+                return Err(NetOptimError::NegativeCycle(cycle));
+            }
+        }
+    }
+
+    overflowed.check(&distances)?;
+    Ok(Paths {
+        distances,
+        predecessors,
+    })
+}
+
+// Classify an arc by its reduced cost: `Some(true)` if negative (the arc improves the
+// target), `Some(false)` if zero, `None` if positive or the source is unreachable.
+// Overflow is handled as in `relaxed_distance`.
+#[inline(always)]
+fn admissible<W: Weight, N>(
+    source: &Option<W>,
+    target: &Option<W>,
+    weight: &W,
+    edge: (usize, usize),
+    overflowed: &mut Overflowed,
+) -> Result<Option<bool>, NetOptimError<N>> {
+    if relaxed_distance(source, target, weight, edge, overflowed)?.is_some() {
+        return Ok(Some(true));
+    }
+    let zero_reduced_cost = match (source, target) {
+        (Some(source), Some(current)) => source
+            .checked_add(weight)
+            .is_some_and(|candidate| !(candidate < *current || *current < candidate)),
+        _ => false,
+    };
+    Ok(zero_reduced_cost.then_some(false))
+}
+
+// A cycle that following predecessors from some node runs into, in arc order.
+fn predecessor_cycle<G>(g: G, predecessors: &[Option<G::NodeId>]) -> Option<Vec<G::NodeId>>
+where
+    G: IntoNodeIdentifiers + NodeIndexable,
+{
+    let ix = |i| g.to_index(i);
+    // Walk on which a node was visited, 0 for none.
+    let mut walk = vec![0; predecessors.len()];
+    for (k, start) in g.node_identifiers().enumerate() {
+        let mut node = start;
+        loop {
+            let x = ix(node);
+            if walk[x] == k + 1 {
+                let mut cycle = vec![node];
+                let mut pred = predecessors[x].expect("cycle nodes have a predecessor");
+                while pred != node {
+                    cycle.push(pred);
+                    pred = predecessors[ix(pred)].expect("cycle nodes have a predecessor");
+                }
+                cycle.reverse();
+                return Some(cycle);
+            }
+            if walk[x] != 0 {
+                break;
+            }
+            walk[x] = k + 1;
+            match predecessors[x] {
+                Some(p) => node = p,
+                None => break,
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bellman_ford;
+    use crate::test_utils::{random_graph, Lcg};
+    use petgraph::prelude::*;
+    use petgraph::Graph;
+
+    #[test]
+    fn test_goldberg_radzik_matches_bellman_ford() {
+        let graph = Graph::<(), i64, Directed>::from_edges([
+            (0, 1, 6),
+            (0, 2, 7),
+            (1, 2, 8),
+            (1, 3, 5),
+            (1, 4, -4),
+            (2, 3, -3),
+            (2, 4, 9),
+            (3, 1, -2),
+            (4, 0, 2),
+            (4, 3, 7),
+        ]);
+        let paths = goldberg_radzik(&graph, NodeIndex::new(0)).unwrap();
+        let expected = bellman_ford(&graph, NodeIndex::new(0)).unwrap();
+        assert_eq!(paths.distances, expected.distances);
+    }
+
+    #[test]
+    fn test_goldberg_radzik_dag_single_pass() {
+        // Node indices run against the topological order.
+        let graph = Graph::<(), i32, Directed>::from_edges([
+            (4, 3, -1),
+            (3, 2, -1),
+            (2, 1, -1),
+            (1, 0, -1),
+            (4, 0, 1),
+        ]);
+        let paths = goldberg_radzik(&graph, NodeIndex::new(4)).unwrap();
+        assert_eq!(
+            paths.distances,
+            vec![Some(-4), Some(-3), Some(-2), Some(-1), Some(0)]
+        );
+        assert_eq!(paths.predecessors[0], Some(NodeIndex::new(1)));
+    }
+
+    #[test]
+    fn test_goldberg_radzik_zero_cycle() {
+        let graph =
+            Graph::<(), i32, Directed>::from_edges([(0, 1, 1), (1, 2, 2), (2, 1, -2), (2, 3, -1)]);
+        let paths = goldberg_radzik(&graph, NodeIndex::new(0)).unwrap();
+        assert_eq!(paths.distances, vec![Some(0), Some(1), Some(3), Some(2)]);
+    }
+
+    #[test]
+    fn test_goldberg_radzik_negative_cycle() {
+        let graph = Graph::<(), f64, Directed>::from_edges([
+            (0, 1, 1.0),
+            (1, 2, 1.0),
+            (2, 3, 1.0),
+            (3, 1, -4.0),
+        ]);
+        let Err(NetOptimError::NegativeCycle(mut cycle)) =
+            goldberg_radzik(&graph, NodeIndex::new(0))
+        else {
+            panic!("expected a negative cycle");
+        };
+        cycle.sort();
+        assert_eq!(
+            cycle,
+            vec![NodeIndex::new(1), NodeIndex::new(2), NodeIndex::new(3)]
+        );
+    }
+
+    #[test]
+    fn test_goldberg_radzik_negative_self_loop() {
+        let graph = Graph::<(), i32, Directed>::from_edges([(0, 1, 1), (1, 1, -1)]);
+        assert_eq!(
+            goldberg_radzik(&graph, NodeIndex::new(0)).unwrap_err(),
+            NetOptimError::NegativeCycle(vec![NodeIndex::new(1)])
+        );
+    }
+
+    #[test]
+    fn test_goldberg_radzik_invalid_source() {
+        let graph = Graph::<(), i32, Directed>::from_edges([(0, 1, 1)]);
+        assert_eq!(
+            goldberg_radzik(&graph, NodeIndex::new(5)).unwrap_err(),
+            NetOptimError::InvalidNode(NodeIndex::new(5))
+        );
+        let empty = Graph::<(), i32, Directed>::new();
+        assert_eq!(
+            goldberg_radzik(&empty, NodeIndex::new(0)).unwrap_err(),
+            NetOptimError::EmptyGraph
+        );
+    }
+
+    #[test]
+    fn test_goldberg_radzik_unreachable_negative_cycle() {
+        let graph = Graph::<(), i32, Directed>::from_edges([(0, 1, 1), (2, 3, -1), (3, 2, -1)]);
+        let paths = goldberg_radzik(&graph, NodeIndex::new(0)).unwrap();
+        assert_eq!(paths.distances, vec![Some(0), Some(1), None, None]);
+    }
+
+    #[test]
+    fn test_goldberg_radzik_agrees_with_bellman_ford_on_random_graphs() {
+        let mut rng = Lcg::new(0x9e37_79b9);
+        for _ in 0..300 {
+            let n = 2 + rng.below(10) as usize;
+            let m = rng.below(30) as usize;
+            let graph = random_graph(&mut rng, n, m, false, |rng| rng.below(12) as i64 - 3);
+            let source = NodeIndex::new(0);
+            match (
+                goldberg_radzik(&graph, source),
+                bellman_ford(&graph, source),
+            ) {
+                (Ok(paths), Ok(expected)) => assert_eq!(paths.distances, expected.distances),
+                (Err(_), Err(_)) => {}
+                _ => panic!("goldberg_radzik and bellman_ford disagree on negative cycles"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_goldberg_radzik_stable_graph_with_holes() {
+        let mut graph = StableDiGraph::<(), i32>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        let c = graph.add_node(());
+        graph.add_edge(a, c, -2);
+        graph.remove_node(b);
+        let paths = goldberg_radzik(&graph, a).unwrap();
+        assert_eq!(paths.distances[c.index()], Some(-2));
+        assert_eq!(paths.predecessors[c.index()], Some(a));
+    }
+}
@@ -7,7 +7,6 @@ use petgraph::prelude::*;
 use crate::dijkstra::{dijkstra, dijkstra_path};
 use crate::neg_cycle::NegCycleFinder;
 use crate::parametric::{MaxParametricSolver, ParametricAPI};
-use petgraph::graph::EdgeReference;
 
 #[test]
 fn test_integration_bellman_ford_with_neg_cycle() {
@@ -32,12 +31,12 @@ fn test_integration_bellman_ford_with_neg_cycle() {
 fn test_integration_neg_cycle_finder_with_parametric() {
     struct TestAPI;
 
-    impl ParametricAPI<(), Ratio<i32>> for TestAPI {
-        fn distance(&self, ratio: &Ratio<i32>, edge: &EdgeReference<Ratio<i32>>) -> Ratio<i32> {
+    impl<E: EdgeRef<Weight = Ratio<i32>>> ParametricAPI<E, Ratio<i32>> for TestAPI {
+        fn distance(&self, ratio: &Ratio<i32>, edge: &E) -> Ratio<i32> {
             *edge.weight() - *ratio
         }
 
-        fn zero_cancel(&self, cycle: &[EdgeReference<Ratio<i32>>]) -> Ratio<i32> {
+        fn zero_cancel(&self, cycle: &[E]) -> Ratio<i32> {
             let sum_a: Ratio<i32> = cycle.iter().map(|e| *e.weight()).sum();
             let sum_b = Ratio::new(cycle.len() as i32, 1);
             sum_a / sum_b
@@ -61,7 +60,7 @@ fn test_integration_neg_cycle_finder_with_parametric() {
 
 #[test]
 fn test_integration_dijkstra_neg_cycle_comparison() {
-    use petgraph::algo::bellman_ford;
+    use crate::bellman_ford;
 
     let graph = Graph::<(), f64, Directed>::from_edges([
         (0, 1, 4.0),
@@ -84,7 +83,7 @@ fn test_integration_dijkstra_neg_cycle_comparison() {
 
 #[test]
 fn test_integration_complete_workflow() {
-    use petgraph::algo::bellman_ford;
+    use crate::bellman_ford;
 
     // Create a complex graph
     let mut graph = Graph::new();
@@ -122,7 +121,7 @@ fn test_integration_complete_workflow() {
     // Test specific path
     let target = nodes[5];
     let path = dijkstra_path(&graph, source, target);
-    assert!(path.is_some());
+    assert!(path.is_ok());
 
     // Verify path cost matches distance
     let path = path.unwrap();
@@ -134,7 +133,7 @@ fn test_integration_complete_workflow() {
         path_cost += graph[edge];
     }
 
-    assert_eq!(path_cost, dijkstra_result.distances[target.index()]);
+    assert_eq!(Some(path_cost), dijkstra_result.distances[target.index()]);
 }
 
 #[test]
@@ -161,11 +160,15 @@ fn test_integration_large_graph_performance() {
     assert_eq!(paths.distances.len(), num_nodes);
 
     // Verify source distance is 0
-    assert_eq!(paths.distances[source.index()], 0.0);
+    assert_eq!(paths.distances[source.index()], Some(0.0));
 
     // Verify all distances are non-negative
     for (i, &dist) in paths.distances.iter().enumerate() {
-        assert!(dist >= 0.0, "Negative distance at node {}", i);
+        assert!(
+            dist.is_some_and(|d| d >= 0.0),
+            "Negative distance at node {}",
+            i
+        );
     }
 }
 
@@ -173,12 +176,12 @@ fn test_integration_large_graph_performance() {
 fn test_integration_parametric_with_neg_cycle_finder() {
     struct LinearAPI;
 
-    impl ParametricAPI<(), Ratio<i32>> for LinearAPI {
-        fn distance(&self, ratio: &Ratio<i32>, edge: &EdgeReference<Ratio<i32>>) -> Ratio<i32> {
+    impl<E: EdgeRef<Weight = Ratio<i32>>> ParametricAPI<E, Ratio<i32>> for LinearAPI {
+        fn distance(&self, ratio: &Ratio<i32>, edge: &E) -> Ratio<i32> {
             *edge.weight() - *ratio
         }
 
-        fn zero_cancel(&self, cycle: &[EdgeReference<Ratio<i32>>]) -> Ratio<i32> {
+        fn zero_cancel(&self, cycle: &[E]) -> Ratio<i32> {
             let sum_a: Ratio<i32> = cycle.iter().map(|e| *e.weight()).sum();
             let sum_b = Ratio::new(cycle.len() as i32, 1);
             sum_a / sum_b
@@ -0,0 +1,261 @@
+//! Johnson's all-pairs shortest paths.
+//!
+//! Node potentials `h` from a Bellman-Ford run with a virtual source (joined to every
+//! node by a zero arc) make every reduced cost `w(u, v) + h(u) - h(v)` non-negative,
+//! so [`dijkstra`](crate::dijkstra::dijkstra) can then be run from every node. This
+//! is O(|V|·|E| log |V|), much faster than |V| Bellman-Ford runs on sparse graphs.
+
+use petgraph::visit::{
+    EdgeRef, IntoEdges, IntoNodeIdentifiers, NodeCount, NodeIndexable, Visitable,
+};
+
+use crate::dijkstra::{dijkstra_by, DijkstraOptions};
+use crate::error::NetOptimError;
+use crate::weight::Weight;
+use crate::{bellman_ford_virtual_source, AllPairsPaths};
+
+/// \[Generic\] Compute shortest paths between all pairs of nodes using
+/// [Johnson's algorithm][johnson].
+///
+/// Negative edge costs are permitted. If the graph has a negative cycle, a
+/// [`NetOptimError::NegativeCycle`] with its nodes is returned; use
+/// [`bellman_ford_virtual_source`] for the full certificate.
+///
+/// Floating point reduced costs that round slightly below zero are clamped to zero. On
+/// exact weight types a negative reduced cost means wrong potentials; it is reported as
+/// [`NetOptimError::AlgorithmError`], as is a reduced cost or distance that overflows the
+/// weight type.
+///
+/// [johnson]: https://en.wikipedia.org/wiki/Johnson%27s_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::prelude::*;
+/// use netoptim_rs::johnson::johnson;
+/// use netoptim_rs::NetOptimError;
+///
+/// let g = Graph::<(), i32>::from_edges(&[(0, 1, 3), (1, 2, -2), (2, 0, 1), (0, 2, 4)]);
+/// let paths = johnson(&g).unwrap();
+/// assert_eq!(paths.distances[0], vec![Some(0), Some(3), Some(1)]);
+/// assert_eq!(paths.distances[2], vec![Some(1), Some(4), Some(0)]);
+/// assert_eq!(paths.predecessors[0][2], Some(NodeIndex::new(1)));
+///
+/// let g = Graph::<(), i32>::from_edges(&[(0, 1, 1), (1, 2, -2), (2, 1, 1)]);
+/// let err = johnson(&g).unwrap_err();
+/// assert!(matches!(err, NetOptimError::NegativeCycle(nodes) if nodes.len() == 2));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn johnson<G>(g: G) -> Result<AllPairsPaths<G::NodeId, G::EdgeWeight>, NetOptimError<G::NodeId>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable + Visitable,
+    G::EdgeWeight: Weight,
+{
+    let ix = |i| g.to_index(i);
+    let n = g.node_bound();
+
+    // Step 1: potentials from a virtual source joined to every node
+    let potentials = bellman_ford_virtual_source(g)?;
+    let h: Vec<G::EdgeWeight> = potentials
+        .distances
+        .into_iter()
+        .map(|p| p.unwrap_or_else(G::EdgeWeight::zero))
+        .collect();
+
+    // Step 2: Dijkstra from every node on the non-negative reduced costs
+    let mut distances = vec![vec![None; n]; n];
+    let mut all_predecessors = vec![vec![None; n]; n];
+    for s in g.node_identifiers() {
+        let sx = ix(s);
+        let reduced_cost = |edge: G::EdgeRef| {
+            let (ux, vx) = (ix(edge.source()), ix(edge.target()));
+            reduced_cost(edge.weight(), &h[ux], &h[vx], (ux, vx)).map(Some)
+        };
+        // Reduced costs are non-negative, so Dijkstra only fails on an overflow.
+        let result = dijkstra_by(g, s, reduced_cost, &DijkstraOptions::new())?;
+
+        // Undo the reweighting: d(s, t) = d'(s, t) - h(s) + h(t), adding h(t) first
+        // unless that leaves the range of the weight type.
+        for (tx, d) in result.distances.into_iter().enumerate() {
+            let Some(d) = d else {
+                continue;
+            };
+            let distance = d
+                .checked_add(&h[tx])
+                .and_then(|d| d.checked_sub(&h[sx]))
+                .or_else(|| d.checked_sub(&h[sx])?.checked_add(&h[tx]))
+                .ok_or_else(|| {
+                    NetOptimError::AlgorithmError(format!(
+                        "distance from node {} to node {} overflows",
+                        sx, tx
+                    ))
+                })?;
+            distances[sx][tx] = Some(distance);
+        }
+        for (tx, p) in result.predecessors.into_iter().enumerate() {
+            all_predecessors[sx][tx] = p;
+        }
+    }
+
+    Ok(AllPairsPaths {
+        distances,
+        predecessors: all_predecessors,
+    })
+}
+
+// The reduced cost `w + h(u) - h(v)` of `edge`. Feasible potentials make it
+// non-negative, so a negative value is rounding noise, clamped to zero, on floating
+// point types and an error on exact ones.
+fn reduced_cost<W: Weight, N>(
+    weight: &W,
+    hu: &W,
+    hv: &W,
+    edge: (usize, usize),
+) -> Result<W, NetOptimError<N>> {
+    let reduced = weight
+        .checked_add(hu)
+        .and_then(|lifted| lifted.checked_sub(hv))
+        .ok_or_else(|| {
+            NetOptimError::AlgorithmError(format!(
+                "reduced cost of edge {} -> {} overflows",
+                edge.0, edge.1
+            ))
+        })?;
+    let zero = W::zero();
+    if reduced < zero {
+        if W::EXACT {
+            return Err(NetOptimError::AlgorithmError(format!(
+                "reduced cost of edge {} -> {} is negative",
+                edge.0, edge.1
+            )));
+        }
+        return Ok(zero);
+    }
+    Ok(reduced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bellman_ford;
+    use petgraph::prelude::*;
+    use petgraph::Graph;
+
+    #[test]
+    fn test_johnson_matches_bellman_ford() {
+        let graph = Graph::<(), i64, Directed>::from_edges([
+            (0, 1, 6),
+            (0, 2, 7),
+            (1, 2, 8),
+            (1, 3, 5),
+            (1, 4, -4),
+            (2, 3, -3),
+            (2, 4, 9),
+            (3, 1, -2),
+            (4, 0, 2),
+            (4, 3, 7),
+        ]);
+        let paths = johnson(&graph).unwrap();
+        for s in graph.node_indices() {
+            let expected = bellman_ford(&graph, s).unwrap();
+            assert_eq!(paths.distances[s.index()], expected.distances);
+        }
+    }
+
+    #[test]
+    fn test_johnson_predecessors_follow_shortest_paths() {
+        let graph = Graph::<(), i32, Directed>::from_edges([
+            (0, 1, 4),
+            (0, 2, 1),
+            (2, 1, -2),
+            (1, 3, 1),
+            (3, 0, 2),
+        ]);
+        let paths = johnson(&graph).unwrap();
+        for s in 0..4 {
+            for t in 0..4 {
+                let Some(p) = paths.predecessors[s][t] else {
+                    continue;
+                };
+                let edge = graph.find_edge(p, NodeIndex::new(t)).unwrap();
+                assert_eq!(
+                    paths.distances[s][t],
+                    Some(paths.distances[s][p.index()].unwrap() + graph[edge])
+                );
+            }
+        }
+        assert_eq!(paths.distances[0][3], Some(0));
+    }
+
+    #[test]
+    fn test_johnson_unreachable() {
+        let graph = Graph::<(), f64, Directed>::from_edges([(0, 1, -1.0), (2, 1, 2.0)]);
+        let paths = johnson(&graph).unwrap();
+        assert_eq!(paths.distances[0], vec![Some(0.0), Some(-1.0), None]);
+        assert_eq!(paths.distances[1], vec![None, Some(0.0), None]);
+        assert_eq!(paths.distances[2], vec![None, Some(2.0), Some(0.0)]);
+    }
+
+    #[test]
+    fn test_johnson_negative_cycle() {
+        let graph =
+            Graph::<(), i32, Directed>::from_edges([(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 1, -4)]);
+        let Err(NetOptimError::NegativeCycle(mut nodes)) = johnson(&graph) else {
+            panic!("expected a negative cycle");
+        };
+        nodes.sort();
+        assert_eq!(
+            nodes,
+            vec![NodeIndex::new(1), NodeIndex::new(2), NodeIndex::new(3)]
+        );
+    }
+
+    #[test]
+    fn test_johnson_unreachable_negative_cycle_is_reported() {
+        // Unlike single-source algorithms, every node is a source here.
+        let graph = Graph::<(), i32, Directed>::from_edges([(0, 1, 1), (2, 3, -1), (3, 2, -1)]);
+        assert!(johnson(&graph).is_err());
+    }
+
+    #[test]
+    fn test_johnson_unsigned_weights() {
+        let graph = Graph::<(), u32, Directed>::from_edges([(0, 1, 2), (1, 2, 3), (0, 2, 9)]);
+        let paths = johnson(&graph).unwrap();
+        assert_eq!(paths.distances[0], vec![Some(0), Some(2), Some(5)]);
+    }
+
+    #[test]
+    fn test_johnson_overflow_is_an_error() {
+        // h = [0, -1, 0], so the reduced cost of 0 -> 1 is i32::MAX + 1.
+        let graph =
+            Graph::<(), i32, Directed>::from_edges([(0, 1, i32::MAX), (2, 1, -1), (2, 0, 5)]);
+        assert!(matches!(
+            johnson(&graph),
+            Err(NetOptimError::AlgorithmError(_))
+        ));
+
+        // Distances up to i32::MAX are exact, whatever the potentials.
+        let graph = Graph::<(), i32, Directed>::from_edges([(0, 1, i32::MAX - 1), (2, 1, -1)]);
+        let paths = johnson(&graph).unwrap();
+        assert_eq!(paths.distances[0][1], Some(i32::MAX - 1));
+        assert_eq!(paths.distances[2][1], Some(-1));
+    }
+
+    #[test]
+    fn test_johnson_negative_reduced_cost() {
+        // h(v) > w + h(u): infeasible potentials on an exact type are an error.
+        let result = reduced_cost::<i32, usize>(&1, &0, &2, (0, 1));
+        assert_eq!(
+            result,
+            Err(NetOptimError::AlgorithmError(
+                "reduced cost of edge 0 -> 1 is negative".to_string()
+            ))
+        );
+        assert_eq!(reduced_cost::<i32, usize>(&1, &0, &1, (0, 1)), Ok(0));
+
+        // Floating point rounding below zero is clamped.
+        let (w, hu, hv) = (0.3, 0.0, 0.1 + 0.2);
+        assert!(w + hu - hv < 0.0);
+        assert_eq!(reduced_cost::<f64, usize>(&w, &hu, &hv, (0, 1)), Ok(0.0));
+    }
+}
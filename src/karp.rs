@@ -0,0 +1,250 @@
+//! Karp's minimum mean cycle algorithm.
+//!
+//! Runs in O(|V|·|E|) time and O(|V|²) memory per strongly connected component.
+//! It is an exact, independent cross-check for the Howard-based
+//! [`MaxParametricSolver`](crate::parametric::MaxParametricSolver), and is often
+//! faster on small dense graphs.
+
+use std::ops::{Add, Div, Sub};
+
+use num::traits::FromPrimitive;
+use petgraph::algo::tarjan_scc;
+use petgraph::visit::{EdgeRef, IntoEdges, IntoNodeIdentifiers, NodeIndexable};
+
+/// A cycle together with its mean edge weight.
+#[derive(Debug, Clone)]
+pub struct MeanCycle<E, D> {
+    /// The mean weight of `cycle`.
+    pub mean: D,
+    /// The edges of the cycle, in forward order.
+    pub cycle: Vec<E>,
+}
+
+/// \[Generic\] Find a minimum mean cycle using [Karp's algorithm][karp].
+///
+/// Each strongly connected component is processed separately and the best cycle
+/// over all components is returned. Edge weights are supplied by `get_weight`, as
+/// for [`NegCycleFinder::howard`](crate::neg_cycle::NegCycleFinder::howard), so exact
+/// types such as `Ratio<i64>` can be used.
+///
+/// Returns `None` if the graph is acyclic.
+///
+/// [karp]: https://doi.org/10.1016/0012-365X(78)90011-0
+///
+/// # Example
+/// ```rust
+/// use petgraph::prelude::*;
+/// use netoptim_rs::karp::min_mean_cycle;
+///
+/// let g = DiGraph::<(), f64>::from_edges([
+///     (0, 1, 1.0),
+///     (1, 0, 3.0), // mean 2
+///     (1, 2, 0.0),
+///     (2, 1, 1.0), // mean 0.5
+/// ]);
+/// let result = min_mean_cycle(&g, |e| *e.weight()).unwrap();
+/// assert_eq!(result.mean, 0.5);
+/// assert_eq!(result.cycle.len(), 2);
+/// ```
+pub fn min_mean_cycle<G, D, F>(g: G, get_weight: F) -> Option<MeanCycle<G::EdgeRef, D>>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    D: Copy + PartialOrd + Add<Output = D> + Sub<Output = D> + Div<Output = D> + FromPrimitive,
+    F: Fn(G::EdgeRef) -> D,
+{
+    let mut best: Option<MeanCycle<G::EdgeRef, D>> = None;
+    let mut local = vec![usize::MAX; g.node_bound()];
+    for scc in tarjan_scc(g) {
+        for (k, &v) in scc.iter().enumerate() {
+            local[g.to_index(v)] = k;
+        }
+        if let Some(found) = karp_scc(g, &scc, &local, &get_weight) {
+            if best.as_ref().is_none_or(|b| found.mean < b.mean) {
+                best = Some(found);
+            }
+        }
+        for &v in &scc {
+            local[g.to_index(v)] = usize::MAX;
+        }
+    }
+    best
+}
+
+/// Karp's algorithm on a single strongly connected component.
+///
+/// `local` maps `to_index` of the component's nodes to their position in `scc`,
+/// and every other node to `usize::MAX`.
+fn karp_scc<G, D, F>(
+    g: G,
+    scc: &[G::NodeId],
+    local: &[usize],
+    get_weight: &F,
+) -> Option<MeanCycle<G::EdgeRef, D>>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    D: Copy + PartialOrd + Add<Output = D> + Sub<Output = D> + Div<Output = D> + FromPrimitive,
+    F: Fn(G::EdgeRef) -> D,
+{
+    let n = scc.len();
+    let loc = |v| local[g.to_index(v)];
+
+    // dist[k][v]: minimum weight of a walk with exactly k edges from scc[0] to v.
+    let mut dist: Vec<Vec<Option<D>>> = vec![vec![None; n]; n + 1];
+    let mut parent: Vec<Vec<Option<G::EdgeRef>>> = vec![vec![None; n]; n + 1];
+    dist[0][0] = D::from_usize(0);
+    for k in 0..n {
+        for (u, &utx) in scc.iter().enumerate() {
+            let Some(du) = dist[k][u] else { continue };
+            for edge in g.edges(utx) {
+                let v = loc(edge.target());
+                if v == usize::MAX {
+                    continue;
+                }
+                let distance = du + get_weight(edge);
+                if dist[k + 1][v].is_none_or(|dv| distance < dv) {
+                    dist[k + 1][v] = Some(distance);
+                    parent[k + 1][v] = Some(edge);
+                }
+            }
+        }
+    }
+
+    // lambda = min_v max_k (dist[n][v] - dist[k][v]) / (n - k)
+    let mut best: Option<(D, usize)> = None;
+    for v in 0..n {
+        let Some(dn) = dist[n][v] else { continue };
+        let mut worst: Option<D> = None;
+        for (k, row) in dist.iter().enumerate().take(n) {
+            let Some(dk) = row[v] else { continue };
+            let mean = (dn - dk) / D::from_usize(n - k)?;
+            if worst.is_none_or(|w| mean > w) {
+                worst = Some(mean);
+            }
+        }
+        if let Some(w) = worst {
+            if best.is_none_or(|(b, _)| w < b) {
+                best = Some((w, v));
+            }
+        }
+    }
+    let (mean, v) = best?;
+
+    // Walk back along the n-edge walk ending at v; it contains a cycle, and every
+    // cycle on it attains the minimum mean.
+    let mut seen = vec![usize::MAX; n];
+    let mut walk = Vec::with_capacity(n);
+    let mut vtx = v;
+    for k in (1..=n).rev() {
+        if seen[vtx] != usize::MAX {
+            let cycle = walk[seen[vtx]..].iter().rev().copied().collect();
+            return Some(MeanCycle { mean, cycle });
+        }
+        seen[vtx] = walk.len();
+        let edge = parent[k][vtx]?;
+        walk.push(edge);
+        vtx = loc(edge.source());
+    }
+    let cycle = walk[seen[vtx]..].iter().rev().copied().collect();
+    Some(MeanCycle { mean, cycle })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parametric::{MaxParametricSolver, ParametricAPI};
+    use num::rational::Ratio;
+    use petgraph::prelude::*;
+
+    fn assert_is_cycle<E: EdgeRef>(cycle: &[E])
+    where
+        E::NodeId: PartialEq + std::fmt::Debug,
+    {
+        for k in 0..cycle.len() {
+            assert_eq!(cycle[k].target(), cycle[(k + 1) % cycle.len()].source());
+        }
+    }
+
+    #[test]
+    fn test_karp_simple_cycle() {
+        let g = DiGraph::<(), f64>::from_edges([(0, 1, 1.0), (1, 2, 2.0), (2, 0, 3.0)]);
+        let result = min_mean_cycle(&g, |e| *e.weight()).unwrap();
+        assert_eq!(result.mean, 2.0);
+        assert_eq!(result.cycle.len(), 3);
+        assert_is_cycle(&result.cycle);
+    }
+
+    #[test]
+    fn test_karp_acyclic() {
+        let g = DiGraph::<(), f64>::from_edges([(0, 1, 1.0), (1, 2, 2.0), (0, 2, 3.0)]);
+        assert!(min_mean_cycle(&g, |e| *e.weight()).is_none());
+    }
+
+    #[test]
+    fn test_karp_self_loop_and_components() {
+        let g = DiGraph::<(), f64>::from_edges([
+            (0, 1, 4.0),
+            (1, 0, 4.0),
+            (1, 2, -10.0),
+            (2, 3, 1.0),
+            (3, 2, 2.0),
+            (3, 3, 1.0),
+        ]);
+        let result = min_mean_cycle(&g, |e| *e.weight()).unwrap();
+        assert_eq!(result.mean, 1.0);
+        assert_eq!(result.cycle.len(), 1);
+        assert_eq!(result.cycle[0].source(), NodeIndex::new(3));
+    }
+
+    #[test]
+    fn test_karp_stable_graph_with_holes() {
+        let mut g = StableDiGraph::<(), f64>::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        g.add_edge(a, c, 1.0);
+        g.add_edge(c, a, 2.0);
+        g.remove_node(b);
+        let result = min_mean_cycle(&g, |e| *e.weight()).unwrap();
+        assert_eq!(result.mean, 1.5);
+        assert_is_cycle(&result.cycle);
+    }
+
+    struct MeanAPI;
+
+    impl<E: EdgeRef<Weight = Ratio<i64>>> ParametricAPI<E, Ratio<i64>> for MeanAPI {
+        fn distance(&self, ratio: &Ratio<i64>, edge: &E) -> Ratio<i64> {
+            *edge.weight() - *ratio
+        }
+
+        fn zero_cancel(&self, cycle: &[E]) -> Ratio<i64> {
+            let total: Ratio<i64> = cycle.iter().map(|e| *e.weight()).sum();
+            total / Ratio::from_integer(cycle.len() as i64)
+        }
+    }
+
+    #[test]
+    fn test_karp_matches_max_parametric_solver() {
+        let r = |n| Ratio::from_integer(n);
+        let g = DiGraph::<(), Ratio<i64>>::from_edges([
+            (0, 1, r(3)),
+            (1, 2, r(-1)),
+            (2, 0, r(5)),
+            (2, 3, r(2)),
+            (3, 4, r(1)),
+            (4, 2, r(0)),
+            (4, 1, r(7)),
+            (3, 0, r(-2)),
+        ]);
+
+        let karp = min_mean_cycle(&g, |e| *e.weight()).unwrap();
+        assert_is_cycle(&karp.cycle);
+        let total: Ratio<i64> = karp.cycle.iter().map(|e| *e.weight()).sum();
+        assert_eq!(total / r(karp.cycle.len() as i64), karp.mean);
+
+        let mut solver = MaxParametricSolver::new(&g, MeanAPI);
+        let mut dist = [r(0); 5];
+        let mut ratio = r(100);
+        solver.run(&mut dist, &mut ratio);
+        assert_eq!(karp.mean, ratio);
+    }
+}
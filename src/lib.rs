@@ -1,64 +1,170 @@
 //! Network optimization algorithms in Rust.
 
+/// Clock skew scheduling for synchronous circuits.
+pub mod clock_skew;
+
+/// Difference constraints solver with infeasibility certificates.
+pub mod difference_constraints;
+
 /// Dijkstra's shortest path algorithm implementation.
 pub mod dijkstra;
 
 /// Error types for network optimization.
 pub mod error;
 
+/// Floyd-Warshall all-pairs shortest paths.
+pub mod floyd_warshall;
+
+/// Goldberg-Radzik shortest path algorithm.
+pub mod goldberg_radzik;
+
+/// Johnson's all-pairs shortest paths.
+pub mod johnson;
+
+/// Karp's minimum mean cycle algorithm.
+pub mod karp;
+
+/// Minimum cost-to-time cycle ratio solver.
+pub mod min_cycle_ratio;
+
+/// Monotone integer priority queues (Dial's buckets and radix heap).
+pub mod monotone_queue;
+
 /// Negative cycle detection using Howard's algorithm.
 pub mod neg_cycle;
 
-/// Maximum parametric optimization.
+/// Constrained negative cycle detection (forward and backward Howard).
+pub mod neg_cycle_q;
+
+/// Optimal diagonal scaling of sparse matrices.
+pub mod optscaling;
+
+/// Maximum and minimum parametric optimization.
 pub mod parametric;
 
+/// Dijkstra's algorithm over a path algebra (shortest, widest, most reliable, minimax).
+pub mod path_algebra;
+
+/// Queue-based Bellman-Ford with subtree disassembly.
+pub mod spfa;
+
 /// Graph utility functions.
 pub mod utils;
 
+/// Edge weight trait for the shortest path algorithms.
+pub mod weight;
+
+/// Yen's k-shortest loopless paths.
+pub mod yen;
+
 pub use error::NetOptimError;
+pub use goldberg_radzik::goldberg_radzik;
+pub use spfa::spfa;
 pub use utils::*;
+pub use weight::Weight;
 
 #[cfg(test)]
 mod integration_tests;
 
+#[cfg(test)]
+mod stable_graph_tests;
+
+#[cfg(test)]
+mod test_utils;
+
 // Logging module - only available with std feature
 #[cfg(feature = "std")]
 pub mod logging;
 
 use petgraph::prelude::*;
 
-use petgraph::algo::{FloatMeasure, NegativeCycle};
-use petgraph::visit::{
-    IntoEdges, IntoNodeIdentifiers, NodeCount, NodeIndexable, VisitMap, Visitable,
-};
+use petgraph::visit::{IntoEdges, IntoNodeIdentifiers, NodeCount, NodeIndexable, Visitable};
 
 /// Result of shortest path algorithms.
 ///
-/// Contains the distances from the source node to all other nodes,
-/// and the predecessor of each node along the shortest path.
+/// Contains the distances from the source node to all other nodes (`None` for
+/// unreachable nodes), and the predecessor of each node along the shortest path.
 #[derive(Debug, Clone)]
 pub struct Paths<NodeId, EdgeWeight> {
-    pub distances: Vec<EdgeWeight>,
+    pub distances: Vec<Option<EdgeWeight>>,
     pub predecessors: Vec<Option<NodeId>>,
 }
 
+/// Result of all-pairs shortest path algorithms.
+///
+/// Both matrices are indexed by the graph's node indices, first by source and then
+/// by target, so that each row is the [`Paths`] of one source.
+#[derive(Debug, Clone)]
+pub struct AllPairsPaths<NodeId, EdgeWeight> {
+    /// `distances[s][t]` is the distance from `s` to `t`, `None` if unreachable.
+    pub distances: Vec<Vec<Option<EdgeWeight>>>,
+    /// `predecessors[s][t]` is the predecessor of `t` on a shortest path from `s`.
+    pub predecessors: Vec<Vec<Option<NodeId>>>,
+}
+
+impl<NodeId, EdgeWeight> AllPairsPaths<NodeId, EdgeWeight>
+where
+    NodeId: Copy + PartialEq,
+    EdgeWeight: Clone,
+{
+    /// The single-source [`Paths`] from the node with index `source`.
+    pub fn paths_from(&self, source: usize) -> Paths<NodeId, EdgeWeight> {
+        Paths {
+            distances: self.distances[source].clone(),
+            predecessors: self.predecessors[source].clone(),
+        }
+    }
+
+    /// Reconstructs a shortest path from `source` to `target` by following the
+    /// predecessor matrix. Returns `None` if `target` is not reachable.
+    ///
+    /// Arguments:
+    ///
+    /// * `g`: The graph the paths were computed on, used to map nodes to indices.
+    /// * `source`, `target`: The end points of the path.
+    pub fn path<G>(&self, g: G, source: NodeId, target: NodeId) -> Option<Vec<NodeId>>
+    where
+        G: NodeIndexable<NodeId = NodeId>,
+    {
+        let s = g.to_index(source);
+        self.distances[s][g.to_index(target)].as_ref()?;
+        let mut path = vec![target];
+        let mut current = target;
+        while current != source {
+            current = self.predecessors[s][g.to_index(current)]?;
+            path.push(current);
+            if path.len() > self.distances.len() {
+                return None;
+            }
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
 /// \[Generic\] Compute shortest paths from node `source` to all other.
 ///
 /// Using the [Bellman–Ford algorithm][bf]; negative edge costs are
 /// permitted, but the graph must not have a cycle of negative weights
 /// (in that case it will return an error).
 ///
-/// On success, return one vec with path costs, and another one which points
-/// out the predecessor of a node along a shortest path. The vectors
-/// are indexed by the graph's node indices.
+/// On success, return one vec with path costs (`None` for nodes that are not
+/// reachable from `source`), and another one which points out the predecessor of
+/// a node along a shortest path. The vectors are indexed by the graph's node indices.
+/// Otherwise return [`NetOptimError::NegativeCycle`] with the nodes of a negative
+/// cycle, or [`NetOptimError::InvalidNode`] if `source` is not a node of `g`.
+///
+/// Any [`Weight`] can be used, so integer and rational costs are computed exactly.
+/// A relaxation that would improve a distance but overflows the weight type is
+/// reported as [`NetOptimError::AlgorithmError`].
 ///
 /// [bf]: https://en.wikipedia.org/wiki/Bellman%E2%80%93Ford_algorithm
 ///
 /// # Example
 /// ```rust
 /// use petgraph::Graph;
-/// use petgraph::algo::bellman_ford;
 /// use petgraph::prelude::*;
+/// use netoptim_rs::bellman_ford;
 ///
 /// let mut g = Graph::new();
 /// let a = g.add_node(()); // node with no weight
@@ -89,7 +195,10 @@ pub struct Paths<NodeId, EdgeWeight> {
 /// let path = bellman_ford(&g, a);
 /// assert!(path.is_ok());
 /// let path = path.unwrap();
-/// assert_eq!(path.distances, vec![    0.0,     2.0,    3.0,    4.0,     5.0,     6.0]);
+/// assert_eq!(
+///     path.distances,
+///     vec![Some(0.0), Some(2.0), Some(3.0), Some(4.0), Some(5.0), Some(6.0)]
+/// );
 /// assert_eq!(path.predecessors, vec![None, Some(a),Some(b),Some(a), Some(d), Some(edge)]);
 ///
 /// // Node f (indice 5) can be reach from a with a path costing 6.
@@ -107,43 +216,229 @@ pub struct Paths<NodeId, EdgeWeight> {
 /// ]);
 ///
 /// assert!(bellman_ford(&graph_with_neg_cycle, NodeIndex::new(0)).is_err());
+///
+/// // Integer weights give exact distances.
+/// let g = Graph::<(), i64>::from_edges(&[(0, 1, 3), (1, 2, -2), (0, 2, 4)]);
+/// let path = bellman_ford(&g, NodeIndex::new(0)).unwrap();
+/// assert_eq!(path.distances, vec![Some(0), Some(3), Some(1)]);
 /// ```
+#[allow(clippy::type_complexity)]
 pub fn bellman_ford<G>(
     g: G,
     source: G::NodeId,
-) -> Result<Paths<G::NodeId, G::EdgeWeight>, NegativeCycle>
+) -> Result<Paths<G::NodeId, G::EdgeWeight>, NetOptimError<G::NodeId>>
 where
     G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable,
-    G::EdgeWeight: FloatMeasure,
+    G::EdgeWeight: Weight,
 {
-    let ix = |i| g.to_index(i);
+    Ok(bellman_ford_with_cycle(g, source)?)
+}
+
+/// Negative cycle reported by [`bellman_ford_with_cycle`] and its multi-source variants.
+///
+/// Carries the cycle itself together with the state of the relaxation when the
+/// cycle was detected. Converts into [`NetOptimError::NegativeCycle`], so `?` works
+/// in functions returning [`NetOptimError`].
+#[derive(Debug, Clone)]
+pub struct NegativeCycleError<NodeId, EdgeRef, EdgeWeight> {
+    /// The nodes of the cycle, in the order they are traversed.
+    pub nodes: Vec<NodeId>,
+    /// The edges of the cycle; `edges[k]` leads from `nodes[k]` to the next node.
+    pub edges: Vec<EdgeRef>,
+    /// The partial distances when the cycle was detected.
+    pub distances: Vec<Option<EdgeWeight>>,
+    /// The partial predecessors when the cycle was detected.
+    pub predecessors: Vec<Option<NodeId>>,
+}
+
+impl<NodeId, EdgeRef, EdgeWeight> From<NegativeCycleError<NodeId, EdgeRef, EdgeWeight>>
+    for NetOptimError<NodeId>
+{
+    fn from(err: NegativeCycleError<NodeId, EdgeRef, EdgeWeight>) -> Self {
+        NetOptimError::NegativeCycle(err.nodes)
+    }
+}
+
+/// Error of [`bellman_ford_with_cycle`] and [`bellman_ford_multi_source`].
+///
+/// Converts into [`NetOptimError`], so `?` works in functions returning it.
+#[derive(Debug, Clone)]
+pub enum BellmanFordError<NodeId, EdgeRef, EdgeWeight> {
+    /// A negative cycle is reachable from the sources.
+    NegativeCycle(NegativeCycleError<NodeId, EdgeRef, EdgeWeight>),
+    /// Any other error: a source that is not a node of the graph
+    /// ([`NetOptimError::InvalidNode`] or [`NetOptimError::EmptyGraph`]), or a distance
+    /// that overflows the weight type ([`NetOptimError::AlgorithmError`]).
+    Error(NetOptimError<NodeId>),
+}
+
+impl<NodeId, EdgeRef, EdgeWeight> From<BellmanFordError<NodeId, EdgeRef, EdgeWeight>>
+    for NetOptimError<NodeId>
+{
+    fn from(err: BellmanFordError<NodeId, EdgeRef, EdgeWeight>) -> Self {
+        match err {
+            BellmanFordError::NegativeCycle(cycle) => cycle.into(),
+            BellmanFordError::Error(err) => err,
+        }
+    }
+}
 
+/// \[Generic\] Compute shortest paths from node `source` to all other, reporting
+/// the offending cycle on failure.
+///
+/// Same as [`bellman_ford`], except that when a negative cycle is reachable from
+/// `source` the error carries its nodes and edges along with the partial distances,
+/// so there is no need to rerun the relaxation through [`find_negative_cycle`]. A
+/// `source` that is not a node of `g`, or an overflowing distance, is reported as
+/// [`BellmanFordError::Error`].
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::prelude::*;
+/// use netoptim_rs::{bellman_ford_with_cycle, BellmanFordError, NetOptimError};
+///
+/// let g = Graph::<(), f64>::from_edges(&[(0, 1, 1.0), (1, 2, 1.0), (2, 1, -3.0)]);
+/// let Err(BellmanFordError::NegativeCycle(cycle)) = bellman_ford_with_cycle(&g, NodeIndex::new(0))
+/// else {
+///     panic!("the cycle 1 -> 2 -> 1 is reachable");
+/// };
+/// assert_eq!(cycle.nodes.len(), 2);
+/// let weight: f64 = cycle.edges.iter().map(|e| *e.weight()).sum();
+/// assert_eq!(weight, -2.0);
+///
+/// let err = bellman_ford_with_cycle(&g, NodeIndex::new(5)).unwrap_err();
+/// assert_eq!(NetOptimError::from(err), NetOptimError::InvalidNode(NodeIndex::new(5)));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn bellman_ford_with_cycle<G>(
+    g: G,
+    source: G::NodeId,
+) -> Result<Paths<G::NodeId, G::EdgeWeight>, BellmanFordError<G::NodeId, G::EdgeRef, G::EdgeWeight>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable,
+    G::EdgeWeight: Weight,
+{
+    bellman_ford_multi_source(g, [(source, G::EdgeWeight::zero())])
+}
+
+/// \[Generic\] Compute shortest paths from a set of sources with initial potentials.
+///
+/// Each `(node, potential)` pair starts `node` at distance `potential`, as if it were
+/// joined by an arc of that weight to a virtual super-source. The result holds, for
+/// every node, the minimum over all sources of `potential + dist(source, node)`, and
+/// the predecessors form a forest rooted at the sources. A node listed more than once
+/// keeps its smallest potential.
+///
+/// A negative cycle reachable from any source, or a source that is not a node of `g`,
+/// is reported as in [`bellman_ford_with_cycle`].
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::prelude::*;
+/// use netoptim_rs::bellman_ford_multi_source;
+///
+/// let g = Graph::<(), i32>::from_edges(&[(0, 2, 5), (1, 2, 1), (2, 3, 1)]);
+/// let sources = [(NodeIndex::new(0), 0), (NodeIndex::new(1), 3)];
+/// let paths = bellman_ford_multi_source(&g, sources).unwrap();
+/// assert_eq!(paths.distances, vec![Some(0), Some(3), Some(4), Some(5)]);
+/// assert_eq!(paths.predecessors[2], Some(NodeIndex::new(1)));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn bellman_ford_multi_source<G, I>(
+    g: G,
+    sources: I,
+) -> Result<Paths<G::NodeId, G::EdgeWeight>, BellmanFordError<G::NodeId, G::EdgeRef, G::EdgeWeight>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable,
+    G::EdgeWeight: Weight,
+    I: IntoIterator<Item = (G::NodeId, G::EdgeWeight)>,
+{
+    let sources: Vec<_> = sources.into_iter().collect();
+    for &(source, _) in &sources {
+        validate_node(g, source).map_err(BellmanFordError::Error)?;
+    }
+    bellman_ford_from(g, sources)
+}
+
+// Bellman-Ford from sources that are known to be nodes of `g`.
+#[allow(clippy::type_complexity)]
+fn bellman_ford_from<G, I>(
+    g: G,
+    sources: I,
+) -> Result<Paths<G::NodeId, G::EdgeWeight>, BellmanFordError<G::NodeId, G::EdgeRef, G::EdgeWeight>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable,
+    G::EdgeWeight: Weight,
+    I: IntoIterator<Item = (G::NodeId, G::EdgeWeight)>,
+{
     // Step 1 and Step 2: initialize and relax
-    let (distances, predecessors) = bellman_ford_initialize_relax(g, source);
+    let (distances, mut predecessors, mut pred_edges) =
+        bellman_ford_initialize_relax(g, sources).map_err(BellmanFordError::Error)?;
 
     // Step 3: check for negative weight cycle
-    for i in g.node_identifiers() {
-        for edge in g.edges(i) {
-            let j = edge.target();
-            let w = *edge.weight();
-            if distances[ix(i)] + w < distances[ix(j)] {
-                return Err(NegativeCycle(()));
-            }
-        }
+    let cycle = bellman_ford_negative_cycle(g, &distances, &mut predecessors, &mut pred_edges)
+        .map_err(BellmanFordError::Error)?;
+    match cycle {
+        Some((nodes, edges)) => Err(BellmanFordError::NegativeCycle(NegativeCycleError {
+            nodes,
+            edges,
+            distances,
+            predecessors,
+        })),
+        None => Ok(Paths {
+            distances,
+            predecessors,
+        }),
     }
+}
 
-    Ok(Paths {
-        distances,
-        predecessors,
-    })
+/// \[Generic\] Compute shortest paths from a virtual super-source joined to every node
+/// by a zero arc.
+///
+/// Every distance is at most zero, and a negative cycle anywhere in the graph is
+/// reported, so this is a whole-graph feasibility check: on success the distances are
+/// potentials `p` with `p(v) <= p(u) + w(u, v)` for every edge. No dummy node has to be
+/// added to the graph. A negative cycle is reported as [`BellmanFordError::NegativeCycle`]
+/// and an overflowing distance as [`BellmanFordError::Error`].
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use netoptim_rs::{bellman_ford_virtual_source, BellmanFordError};
+///
+/// let g = Graph::<(), i32>::from_edges(&[(0, 1, -2), (1, 2, 3), (3, 2, -1)]);
+/// let paths = bellman_ford_virtual_source(&g).unwrap();
+/// assert_eq!(paths.distances, vec![Some(0), Some(-2), Some(-1), Some(0)]);
+///
+/// // The cycle 2 -> 3 -> 2 is not reachable from node 0, but is still found.
+/// let g = Graph::<(), i32>::from_edges(&[(0, 1, 1), (2, 3, 1), (3, 2, -2)]);
+/// let Err(BellmanFordError::NegativeCycle(cycle)) = bellman_ford_virtual_source(&g) else {
+///     panic!("the cycle 2 -> 3 -> 2 is in the graph");
+/// };
+/// assert_eq!(cycle.nodes.len(), 2);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn bellman_ford_virtual_source<G>(
+    g: G,
+) -> Result<Paths<G::NodeId, G::EdgeWeight>, BellmanFordError<G::NodeId, G::EdgeRef, G::EdgeWeight>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable,
+    G::EdgeWeight: Weight,
+{
+    bellman_ford_from(g, g.node_identifiers().map(|v| (v, G::EdgeWeight::zero())))
 }
 
 /// \[Generic\] Find the path of a negative cycle reachable from node `source`.
 ///
 /// Using the [find_negative_cycle][nc]; will search the Graph for negative cycles using
-/// [Bellman–Ford algorithm][bf]. If no negative cycle is found the function will return `None`.
+/// [Bellman–Ford algorithm][bf]. If no negative cycle is found the function will return
+/// `Ok(None)`.
 ///
 /// If a negative cycle is found from source, return one vec with a path of `NodeId`s.
+/// If `source` is not a node of `g`, return [`NetOptimError::InvalidNode`], and if a
+/// distance overflows the weight type, [`NetOptimError::AlgorithmError`].
 ///
 /// The time complexity of this algorithm should be the same as the Bellman-Ford (O(|V|·|E|)).
 ///
@@ -153,8 +448,8 @@ where
 /// # Example
 /// ```rust
 /// use petgraph::Graph;
-/// use petgraph::algo::find_negative_cycle;
 /// use petgraph::prelude::*;
+/// use netoptim_rs::find_negative_cycle;
 ///
 /// let graph_with_neg_cycle = Graph::<(), f32, Directed>::from_edges(&[
 ///         (0, 1, 1.),
@@ -168,14 +463,14 @@ where
 /// let path = find_negative_cycle(&graph_with_neg_cycle, NodeIndex::new(0));
 /// assert_eq!(
 ///     path,
-///     Some([NodeIndex::new(1), NodeIndex::new(3), NodeIndex::new(2)].to_vec())
+///     Ok(Some([NodeIndex::new(1), NodeIndex::new(3), NodeIndex::new(2)].to_vec()))
 /// );
 /// ```
 /// # Example: Graph with no negative cycle
 /// ```rust
 /// use petgraph::Graph;
-/// use petgraph::algo::find_negative_cycle;
 /// use petgraph::prelude::*;
+/// use netoptim_rs::find_negative_cycle;
 ///
 /// let graph = Graph::<(), f32, Directed>::from_edges(&[
 ///     (0, 1, 1.0),
@@ -183,29 +478,150 @@ where
 ///     (2, 3, 1.0),
 /// ]);
 /// let result = find_negative_cycle(&graph, NodeIndex::new(0));
-/// assert!(result.is_none());
+/// assert_eq!(result, Ok(None));
 /// ```
-pub fn find_negative_cycle<G>(g: G, source: G::NodeId) -> Option<Vec<G::NodeId>>
+#[allow(clippy::type_complexity)]
+pub fn find_negative_cycle<G>(
+    g: G,
+    source: G::NodeId,
+) -> Result<Option<Vec<G::NodeId>>, NetOptimError<G::NodeId>>
 where
     G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable + Visitable,
-    G::EdgeWeight: FloatMeasure,
+    G::EdgeWeight: Weight,
 {
-    let ix = |i| g.to_index(i);
-    let mut path = Vec::<G::NodeId>::new();
+    validate_node(g, source)?;
 
     // Step 1: initialize and relax
-    let (distance, predecessor) = bellman_ford_initialize_relax(g, source);
+    let (distance, mut predecessor, mut pred_edge) =
+        bellman_ford_initialize_relax(g, [(source, G::EdgeWeight::zero())])?;
+
+    // Step 2 and Step 3: check for negative weight cycle and walk back to it
+    Ok(
+        bellman_ford_negative_cycle(g, &distance, &mut predecessor, &mut pred_edge)?
+            .map(|(nodes, _)| nodes),
+    )
+}
+
+// Check that `node` is a node of `g`, before it is used to index per-node arrays.
+pub(crate) fn validate_node<G>(g: G, node: G::NodeId) -> Result<(), NetOptimError<G::NodeId>>
+where
+    G: NodeCount + IntoNodeIdentifiers + NodeIndexable,
+{
+    let index = g.to_index(node);
+    if g.node_count() == 0 {
+        Err(NetOptimError::EmptyGraph)
+    } else if index >= g.node_bound() {
+        Err(NetOptimError::InvalidNode(node))
+    } else if g.node_count() < g.node_bound()
+        && !g.node_identifiers().any(|v| g.to_index(v) == index)
+    {
+        // An index hole, such as a node removed from a `StableGraph`.
+        Err(NetOptimError::InvalidNode(node))
+    } else {
+        Ok(())
+    }
+}
+
+// Perform Step 1 and Step 2 of the Bellman-Ford algorithm. Every node in `sources`
+// starts at its given potential, as if joined by an arc of that weight to a virtual
+// source.
+#[inline(always)]
+#[allow(clippy::type_complexity)]
+pub(crate) fn bellman_ford_initialize_relax<G, I>(
+    g: G,
+    sources: I,
+) -> Result<
+    (
+        Vec<Option<G::EdgeWeight>>,
+        Vec<Option<G::NodeId>>,
+        Vec<Option<G::EdgeRef>>,
+    ),
+    NetOptimError<G::NodeId>,
+>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable,
+    G::EdgeWeight: Weight,
+    I: IntoIterator<Item = (G::NodeId, G::EdgeWeight)>,
+{
+    // Step 1: initialize graph
+    let mut predecessor = vec![None; g.node_bound()];
+    let mut pred_edge = vec![None; g.node_bound()];
+    let mut distance = vec![None; g.node_bound()];
+    let ix = |i| g.to_index(i);
+    for (source, potential) in sources {
+        let d = &mut distance[ix(source)];
+        if d.as_ref().is_none_or(|current| potential < *current) {
+            *d = Some(potential);
+        }
+    }
+
+    // Step 2: relax edges repeatedly
+    let mut overflowed = Overflowed::default();
+    for _ in 1..g.node_count() {
+        let mut did_update = false;
+        for i in g.node_identifiers() {
+            for edge in g.edges(i) {
+                let j = edge.target();
+                let edge_ix = (ix(i), ix(j));
+                let relaxed = relaxed_distance(
+                    &distance[ix(i)],
+                    &distance[ix(j)],
+                    edge.weight(),
+                    edge_ix,
+                    &mut overflowed,
+                );
+                let Some(d) = relaxed? else {
+                    continue;
+                };
+                distance[ix(j)] = Some(d);
+                predecessor[ix(j)] = Some(i);
+                pred_edge[ix(j)] = Some(edge);
+                did_update = true;
+            }
+        }
+        if !did_update {
+            break;
+        }
+    }
+    overflowed.check(&distance)?;
+    Ok((distance, predecessor, pred_edge))
+}
 
-    // Step 2: Check for negative weight cycle
-    'outer: for i in g.node_identifiers() {
+// Perform Step 3 of the Bellman-Ford algorithm: look for an edge that can still be
+// relaxed and, if there is one, walk the predecessor chain back to the negative cycle.
+// Returns the cycle's nodes and edges in traversal order.
+#[allow(clippy::type_complexity)]
+pub(crate) fn bellman_ford_negative_cycle<G>(
+    g: G,
+    distance: &[Option<G::EdgeWeight>],
+    predecessor: &mut [Option<G::NodeId>],
+    pred_edge: &mut [Option<G::EdgeRef>],
+) -> Result<Option<(Vec<G::NodeId>, Vec<G::EdgeRef>)>, NetOptimError<G::NodeId>>
+where
+    G: IntoNodeIdentifiers + IntoEdges + NodeIndexable,
+    G::EdgeWeight: Weight,
+{
+    let ix = |i| g.to_index(i);
+    for i in g.node_identifiers() {
         for edge in g.edges(i) {
             let j = edge.target();
-            let w = *edge.weight();
-            if distance[ix(i)] + w < distance[ix(j)] {
-                // Step 3: negative cycle found
+            let edge_ix = (ix(i), ix(j));
+            let relaxed = relaxed_distance(
+                &distance[ix(i)],
+                &distance[ix(j)],
+                edge.weight(),
+                edge_ix,
+                &mut Overflowed::default(),
+            );
+            if relaxed?.is_some() {
+                // Negative cycle found; the violating edge is part of it or leads to it
+                predecessor[ix(j)] = Some(i);
+                pred_edge[ix(j)] = Some(edge);
+
+                let mut path = Vec::<G::NodeId>::new();
                 let start = j;
                 let mut node = start;
-                let mut visited = g.visit_map();
+                let mut visited = vec![false; g.node_bound()];
                 // Go backward in the predecessor chain
                 loop {
                     let ancestor = match predecessor[ix(node)] {
@@ -219,7 +635,7 @@ where
                         break;
                     }
                     // 2. some node was reached twice
-                    else if visited.is_visited(&ancestor) {
+                    else if visited[ix(ancestor)] {
                         // Drop any node in path that is before the first ancestor
                         let pos = path
                             .iter()
@@ -232,59 +648,91 @@ where
 
                     // None of the above, some middle path node
                     path.push(ancestor);
-                    visited.visit(ancestor);
+                    visited[ix(ancestor)] = true;
                     node = ancestor;
                 }
-                // We are done here
-                break 'outer;
+
+                // Users will probably need to follow the path of the negative cycle
+                // so it should be in the reverse order than it was found by the algorithm.
+                path.reverse();
+                let edges = (1..=path.len())
+                    .map(|k| {
+                        pred_edge[ix(path[k % path.len()])]
+                            .expect("every node on the cycle has a predecessor edge")
+                    })
+                    .collect();
+                return Ok(Some((path, edges)));
             }
         }
     }
-    if !path.is_empty() {
-        // Users will probably need to follow the path of the negative cycle
-        // so it should be in the reverse order than it was found by the algorithm.
-        path.reverse();
-        Some(path)
-    } else {
-        None
+    Ok(None)
+}
+
+// Edges, as `(source, target)` indices, whose relaxation went past the top of the
+// weight type. Such a sum cannot beat a representable distance, so it is skipped, but
+// a target that ends up with no distance at all is reachable only through overflow.
+#[derive(Debug, Default)]
+pub(crate) struct Overflowed(Vec<(usize, usize)>);
+
+impl Overflowed {
+    pub(crate) fn push(&mut self, edge: (usize, usize)) {
+        self.0.push(edge);
+    }
+
+    // Fails if the target of a skipped edge was left without a distance.
+    pub(crate) fn check<W, N>(&self, distances: &[Option<W>]) -> Result<(), NetOptimError<N>> {
+        match self.0.iter().find(|&&(_, v)| distances[v].is_none()) {
+            Some(&edge) => Err(overflow_error(edge)),
+            None => Ok(()),
+        }
     }
 }
 
-// Perform Step 1 and Step 2 of the Bellman-Ford algorithm.
+pub(crate) fn overflow_error<N>(edge: (usize, usize)) -> NetOptimError<N> {
+    NetOptimError::AlgorithmError(format!(
+        "distance via edge {} -> {} overflows",
+        edge.0, edge.1
+    ))
+}
+
+// Distance to the target of `edge` through its source, if it improves on the target's
+// current distance. Unreachable sources never improve.
 #[inline(always)]
-fn bellman_ford_initialize_relax<G>(
-    g: G,
-    source: G::NodeId,
-) -> (Vec<G::EdgeWeight>, Vec<Option<G::NodeId>>)
-where
-    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable,
-    G::EdgeWeight: FloatMeasure,
-{
-    // Step 1: initialize graph
-    let mut predecessor = vec![None; g.node_bound()];
-    let mut distance = vec![<_>::infinite(); g.node_bound()];
-    let ix = |i| g.to_index(i);
-    distance[ix(source)] = <_>::zero();
+pub(crate) fn relaxed_distance<W: Weight, N>(
+    source: &Option<W>,
+    target: &Option<W>,
+    weight: &W,
+    edge: (usize, usize),
+    overflowed: &mut Overflowed,
+) -> Result<Option<W>, NetOptimError<N>> {
+    match source {
+        Some(source) => improved_distance(source, target, weight, edge, overflowed),
+        None => Ok(None),
+    }
+}
 
-    // Step 2: relax edges repeatedly
-    for _ in 1..g.node_count() {
-        let mut did_update = false;
-        for i in g.node_identifiers() {
-            for edge in g.edges(i) {
-                let j = edge.target();
-                let w = *edge.weight();
-                if distance[ix(i)] + w < distance[ix(j)] {
-                    distance[ix(j)] = distance[ix(i)] + w;
-                    predecessor[ix(j)] = Some(i);
-                    did_update = true;
-                }
-            }
+// Distance `source + weight` to the target of `edge`, if it improves on the target's
+// current distance. A sum past the top of the weight type is recorded in `overflowed`;
+// one past the bottom always improves, so it is an error.
+#[inline(always)]
+pub(crate) fn improved_distance<W: Weight, N>(
+    source: &W,
+    target: &Option<W>,
+    weight: &W,
+    edge: (usize, usize),
+    overflowed: &mut Overflowed,
+) -> Result<Option<W>, NetOptimError<N>> {
+    match source.checked_add(weight) {
+        Some(candidate) => {
+            let improves = target.as_ref().is_none_or(|current| candidate < *current);
+            Ok(improves.then_some(candidate))
         }
-        if !did_update {
-            break;
+        None if W::zero() <= *weight => {
+            overflowed.push(edge);
+            Ok(None)
         }
+        None => Err(overflow_error(edge)),
     }
-    (distance, predecessor)
 }
 
 #[cfg(test)]
@@ -292,6 +740,15 @@ mod tests {
     use super::*;
     use petgraph::Graph;
 
+    fn negative_cycle<N, E, W>(
+        result: Result<Paths<N, W>, BellmanFordError<N, E, W>>,
+    ) -> NegativeCycleError<N, E, W> {
+        match result {
+            Err(BellmanFordError::NegativeCycle(cycle)) => cycle,
+            _ => panic!("expected a negative cycle"),
+        }
+    }
+
     #[test]
     fn test_bellman_ford_negative_cycle() {
         let graph_with_neg_cycle =
@@ -307,7 +764,7 @@ mod tests {
         let result = bellman_ford(&graph, n0);
         assert!(result.is_ok());
         let paths = result.unwrap();
-        assert_eq!(paths.distances, vec![0.0]);
+        assert_eq!(paths.distances, vec![Some(0.0)]);
         assert_eq!(paths.predecessors, vec![None]);
     }
 
@@ -324,9 +781,9 @@ mod tests {
         let paths = result.unwrap();
         // Node 2 is unreachable, so its distance should be infinite
         assert_eq!(paths.distances.len(), 3);
-        assert_eq!(paths.distances[n0.index()], 0.0);
-        assert_eq!(paths.distances[n1.index()], 1.0);
-        assert!(paths.distances[n2.index()].is_infinite());
+        assert_eq!(paths.distances[n0.index()], Some(0.0));
+        assert_eq!(paths.distances[n1.index()], Some(1.0));
+        assert!(paths.distances[n2.index()].is_none());
         assert_eq!(paths.predecessors, vec![None, Some(n0), None]);
     }
 
@@ -334,7 +791,7 @@ mod tests {
     fn test_find_negative_cycle_exists() {
         let graph_with_neg_cycle =
             Graph::<(), f32, Directed>::from_edges([(0, 1, 1.0), (1, 2, 1.0), (2, 0, -3.0)]);
-        let result = find_negative_cycle(&graph_with_neg_cycle, NodeIndex::new(0));
+        let result = find_negative_cycle(&graph_with_neg_cycle, NodeIndex::new(0)).unwrap();
         assert!(result.is_some());
         let cycle = result.unwrap();
         assert_eq!(cycle.len(), 3);
@@ -347,7 +804,7 @@ mod tests {
     fn test_find_negative_cycle_none() {
         let graph = Graph::<(), f32, Directed>::from_edges([(0, 1, 1.0), (1, 2, 1.0), (2, 3, 1.0)]);
         let result = find_negative_cycle(&graph, NodeIndex::new(0));
-        assert!(result.is_none());
+        assert_eq!(result, Ok(None));
     }
 
     #[test]
@@ -355,12 +812,239 @@ mod tests {
         let graph =
             Graph::<(), f32, Directed>::from_edges([(0, 1, 1.0), (2, 3, -1.0), (3, 2, -1.0)]);
         let result = find_negative_cycle(&graph, NodeIndex::new(0));
-        assert!(result.is_none());
+        assert_eq!(result, Ok(None));
+    }
+
+    #[test]
+    fn test_bellman_ford_with_cycle_reports_cycle() {
+        let graph = Graph::<(), f32, Directed>::from_edges([
+            (0, 1, 1.0),
+            (1, 2, 1.0),
+            (2, 3, 1.0),
+            (3, 1, -4.0),
+            (3, 4, 1.0),
+        ]);
+        let err = negative_cycle(bellman_ford_with_cycle(&graph, NodeIndex::new(0)));
+        assert_eq!(err.nodes.len(), 3);
+        assert_eq!(err.edges.len(), 3);
+        for (k, edge) in err.edges.iter().enumerate() {
+            assert_eq!(edge.source(), err.nodes[k]);
+            assert_eq!(edge.target(), err.nodes[(k + 1) % err.nodes.len()]);
+        }
+        let weight: f32 = err.edges.iter().map(|e| *e.weight()).sum();
+        assert_eq!(weight, -2.0);
+        assert_eq!(err.distances.len(), 5);
+        assert_eq!(err.predecessors.len(), 5);
+    }
+
+    #[test]
+    fn test_bellman_ford_with_cycle_self_loop() {
+        let graph = Graph::<(), f32, Directed>::from_edges([(0, 1, 1.0), (1, 1, -1.0)]);
+        let err = negative_cycle(bellman_ford_with_cycle(&graph, NodeIndex::new(0)));
+        assert_eq!(err.nodes, vec![NodeIndex::new(1)]);
+        assert_eq!(err.edges.len(), 1);
+        assert_eq!(*err.edges[0].weight(), -1.0);
+    }
+
+    #[test]
+    fn test_bellman_ford_with_cycle_matches_bellman_ford() {
+        let graph =
+            Graph::<(), f32, Directed>::from_edges([(0, 1, 2.0), (1, 2, -1.0), (0, 2, 3.0)]);
+        let paths = bellman_ford_with_cycle(&graph, NodeIndex::new(0)).unwrap();
+        let expected = bellman_ford(&graph, NodeIndex::new(0)).unwrap();
+        assert_eq!(paths.distances, expected.distances);
+        assert_eq!(paths.predecessors, expected.predecessors);
+    }
+
+    #[test]
+    fn test_bellman_ford_integer_weights() {
+        let graph = Graph::<(), i64, Directed>::from_edges([
+            (0, 1, 4),
+            (0, 2, 5),
+            (1, 2, -3),
+            (2, 3, 2),
+            (4, 3, 1),
+        ]);
+        let paths = bellman_ford(&graph, NodeIndex::new(0)).unwrap();
+        assert_eq!(
+            paths.distances,
+            vec![Some(0), Some(4), Some(1), Some(3), None]
+        );
+        assert_eq!(paths.predecessors[2], Some(NodeIndex::new(1)));
+        assert_eq!(paths.predecessors[4], None);
+    }
+
+    #[test]
+    fn test_bellman_ford_exact_rational_weights() {
+        // 1/10 is not exact in binary floating point, so ten of them do not sum to one.
+        let tenth = Ratio::new(1_i64, 10);
+        let mut edges: Vec<(u32, u32, Ratio<i64>)> = (0..10).map(|k| (k, k + 1, tenth)).collect();
+        edges.push((0, 10, Ratio::from_integer(1)));
+        let graph = Graph::<(), Ratio<i64>, Directed>::from_edges(edges);
+        let paths = bellman_ford(&graph, NodeIndex::new(0)).unwrap();
+        assert_eq!(paths.distances[10], Some(Ratio::from_integer(1)));
+    }
+
+    #[test]
+    fn test_bellman_ford_bigint_weights() {
+        use num::bigint::BigInt;
+
+        let big = BigInt::from(i64::MAX);
+        let graph =
+            Graph::<(), BigInt, Directed>::from_edges([(0, 1, big.clone()), (1, 2, big.clone())]);
+        let paths = bellman_ford(&graph, NodeIndex::new(0)).unwrap();
+        assert_eq!(paths.distances[2], Some(big * BigInt::from(2)));
+    }
+
+    #[test]
+    fn test_find_negative_cycle_integer_weights() {
+        let graph =
+            Graph::<(), i32, Directed>::from_edges([(0, 1, 1), (1, 2, 1), (2, 1, -2), (2, 3, 1)]);
+        let cycle = find_negative_cycle(&graph, NodeIndex::new(0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(cycle.len(), 2);
+        assert!(cycle.contains(&NodeIndex::new(1)));
+        assert!(cycle.contains(&NodeIndex::new(2)));
+    }
+
+    #[test]
+    fn test_bellman_ford_multi_source_potentials() {
+        let graph =
+            Graph::<(), i32, Directed>::from_edges([(0, 2, 4), (1, 2, 1), (2, 3, 2), (1, 0, 1)]);
+        let (n0, n1) = (NodeIndex::new(0), NodeIndex::new(1));
+        let paths = bellman_ford_multi_source(&graph, [(n0, 0), (n1, 2)]).unwrap();
+        assert_eq!(paths.distances, vec![Some(0), Some(2), Some(3), Some(5)]);
+        assert_eq!(
+            paths.predecessors,
+            vec![None, None, Some(n1), Some(2.into())]
+        );
+
+        // A source can be improved through another source.
+        let paths = bellman_ford_multi_source(&graph, [(n0, 5), (n1, 0)]).unwrap();
+        assert_eq!(paths.distances[0], Some(1));
+        assert_eq!(paths.predecessors[0], Some(n1));
+    }
+
+    #[test]
+    fn test_bellman_ford_multi_source_duplicate_source_keeps_minimum() {
+        let graph = Graph::<(), i32, Directed>::from_edges([(0, 1, 1)]);
+        let n0 = NodeIndex::new(0);
+        let paths = bellman_ford_multi_source(&graph, [(n0, 3), (n0, -1), (n0, 2)]).unwrap();
+        assert_eq!(paths.distances, vec![Some(-1), Some(0)]);
+    }
+
+    #[test]
+    fn test_bellman_ford_multi_source_single_matches_bellman_ford() {
+        let graph = Graph::<(), f64, Directed>::from_edges([
+            (0, 1, 2.0),
+            (1, 2, -1.0),
+            (0, 2, 3.0),
+            (3, 0, 1.0),
+        ]);
+        let source = NodeIndex::new(0);
+        let paths = bellman_ford_multi_source(&graph, [(source, 0.0)]).unwrap();
+        let expected = bellman_ford(&graph, source).unwrap();
+        assert_eq!(paths.distances, expected.distances);
+        assert_eq!(paths.predecessors, expected.predecessors);
+    }
+
+    #[test]
+    fn test_bellman_ford_virtual_source_potentials_are_feasible() {
+        let graph = Graph::<(), i64, Directed>::from_edges([
+            (0, 1, 6),
+            (1, 3, 5),
+            (1, 4, -4),
+            (2, 3, -3),
+            (3, 1, -2),
+            (4, 0, 2),
+            (5, 2, -7),
+        ]);
+        let paths = bellman_ford_virtual_source(&graph).unwrap();
+        for edge in graph.edge_references() {
+            let p = |v: NodeIndex| paths.distances[v.index()].unwrap();
+            assert!(p(edge.target()) <= p(edge.source()) + edge.weight());
+        }
+        assert!(paths.distances.iter().all(|d| d.unwrap() <= 0));
+        assert_eq!(paths.distances[5], Some(0));
+    }
+
+    #[test]
+    fn test_bellman_ford_virtual_source_finds_unreachable_cycle() {
+        let graph = Graph::<(), i32, Directed>::from_edges([(0, 1, 1), (2, 3, -1), (3, 2, -1)]);
+        assert!(bellman_ford(&graph, NodeIndex::new(0)).is_ok());
+        let err = negative_cycle(bellman_ford_virtual_source(&graph));
+        let weight: i32 = err.edges.iter().map(|e| *e.weight()).sum();
+        assert_eq!(weight, -2);
+    }
+
+    #[test]
+    fn test_bellman_ford_overflow_is_an_error() {
+        // Node 2 is at -200, past the range of i8, and 1 -> 2 -> 1 is a negative cycle:
+        // neither may be lost to the overflow.
+        let graph = Graph::<(), i8, Directed>::from_edges([(0, 1, -100), (1, 2, -100), (2, 1, 50)]);
+        let s = NodeIndex::new(0);
+        let is_overflow =
+            |err: NetOptimError<NodeIndex>| matches!(err, NetOptimError::AlgorithmError(_));
+        assert!(is_overflow(bellman_ford(&graph, s).unwrap_err()));
+        assert!(is_overflow(spfa(&graph, s).unwrap_err()));
+        assert!(is_overflow(goldberg_radzik(&graph, s).unwrap_err()));
+        assert!(is_overflow(find_negative_cycle(&graph, s).unwrap_err()));
+        assert!(is_overflow(
+            bellman_ford_virtual_source(&graph).unwrap_err().into()
+        ));
+
+        // Past the top of the type, a sum cannot improve on a known distance.
+        let graph = Graph::<(), u8, Directed>::from_edges([(0, 1, 1), (0, 2, 2), (1, 2, 255)]);
+        let paths = bellman_ford(&graph, s).unwrap();
+        assert_eq!(paths.distances, vec![Some(0), Some(1), Some(2)]);
+        assert_eq!(spfa(&graph, s).unwrap().distances, paths.distances);
+        assert_eq!(
+            goldberg_radzik(&graph, s).unwrap().distances,
+            paths.distances
+        );
+
+        // Node 2 is reached only through 200 + 100, which does not fit a u8.
+        let graph = Graph::<(), u8, Directed>::from_edges([(0, 1, 200), (1, 2, 100)]);
+        assert!(is_overflow(bellman_ford(&graph, s).unwrap_err()));
+        assert!(is_overflow(spfa(&graph, s).unwrap_err()));
+        assert!(is_overflow(goldberg_radzik(&graph, s).unwrap_err()));
+    }
+
+    #[test]
+    fn test_bellman_ford_invalid_source() {
+        let graph = Graph::<(), i32, Directed>::from_edges([(0, 1, 1)]);
+        let bad = NodeIndex::new(9);
+        let invalid = NetOptimError::InvalidNode(bad);
+        assert_eq!(bellman_ford(&graph, bad).unwrap_err(), invalid);
+        assert_eq!(find_negative_cycle(&graph, bad), Err(invalid.clone()));
+        assert!(matches!(
+            bellman_ford_with_cycle(&graph, bad),
+            Err(BellmanFordError::Error(err)) if err == invalid
+        ));
+        let sources = [(NodeIndex::new(0), 0), (bad, 0)];
+        assert!(matches!(
+            bellman_ford_multi_source(&graph, sources),
+            Err(BellmanFordError::Error(err)) if err == invalid
+        ));
+
+        let empty = Graph::<(), i32, Directed>::new();
+        assert_eq!(
+            find_negative_cycle(&empty, NodeIndex::new(0)),
+            Err(NetOptimError::EmptyGraph)
+        );
+    }
+
+    #[test]
+    fn test_bellman_ford_virtual_source_empty_graph() {
+        let graph = Graph::<(), i32, Directed>::new();
+        let paths = bellman_ford_virtual_source(&graph).unwrap();
+        assert!(paths.distances.is_empty());
     }
     use crate::neg_cycle::NegCycleFinder;
     use crate::parametric::{MaxParametricSolver, ParametricAPI};
     use num::rational::Ratio;
-    use petgraph::graph::{DiGraph, EdgeReference};
+    use petgraph::graph::DiGraph;
 
     #[test]
     fn test_neg_cycle_multiple_neg_cycles() {
@@ -406,12 +1090,12 @@ mod tests {
 
     struct TestParametricAPI;
 
-    impl ParametricAPI<(), Ratio<i32>> for TestParametricAPI {
-        fn distance(&self, ratio: &Ratio<i32>, edge: &EdgeReference<Ratio<i32>>) -> Ratio<i32> {
+    impl<E: EdgeRef<Weight = Ratio<i32>>> ParametricAPI<E, Ratio<i32>> for TestParametricAPI {
+        fn distance(&self, ratio: &Ratio<i32>, edge: &E) -> Ratio<i32> {
             *edge.weight() - *ratio
         }
 
-        fn zero_cancel(&self, cycle: &[EdgeReference<Ratio<i32>>]) -> Ratio<i32> {
+        fn zero_cancel(&self, cycle: &[E]) -> Ratio<i32> {
             let mut sum_a = Ratio::new(0, 1);
             let mut sum_b = Ratio::new(0, 1);
             for edge in cycle {
@@ -476,7 +1160,7 @@ mod tests {
         let result = bellman_ford(&graph, n0);
         assert!(result.is_ok());
         let paths = result.unwrap();
-        assert_eq!(paths.distances, vec![0.0]);
+        assert_eq!(paths.distances, vec![Some(0.0)]);
         assert_eq!(paths.predecessors, vec![None]);
     }
 
@@ -493,9 +1177,9 @@ mod tests {
         let paths = result.unwrap();
         // Node 2 is unreachable, so its distance should be infinite
         assert_eq!(paths.distances.len(), 3);
-        assert_eq!(paths.distances[n0.index()], 0.0);
-        assert_eq!(paths.distances[n1.index()], 1.0);
-        assert!(paths.distances[n2.index()].is_infinite());
+        assert_eq!(paths.distances[n0.index()], Some(0.0));
+        assert_eq!(paths.distances[n1.index()], Some(1.0));
+        assert!(paths.distances[n2.index()].is_none());
         assert_eq!(paths.predecessors, vec![None, Some(n0), None]);
     }
 
@@ -507,7 +1191,7 @@ mod tests {
             (2, 3, 1.0),
             (3, 2, -3.0),
         ]);
-        let result = find_negative_cycle(&graph_with_neg_cycle, NodeIndex::new(0));
+        let result = find_negative_cycle(&graph_with_neg_cycle, NodeIndex::new(0)).unwrap();
         assert!(result.is_some());
     }
 
@@ -515,7 +1199,7 @@ mod tests {
     fn test_find_negative_cycle_no_neg_cycle() {
         let graph = Graph::<(), f32, Directed>::from_edges([(0, 1, 1.0), (1, 2, 1.0), (2, 3, 1.0)]);
         let result = find_negative_cycle(&graph, NodeIndex::new(0));
-        assert!(result.is_none());
+        assert_eq!(result, Ok(None));
     }
 
     #[test]
@@ -523,6 +1207,6 @@ mod tests {
         let graph =
             Graph::<(), f32, Directed>::from_edges([(0, 1, 1.0), (2, 3, -1.0), (3, 2, -1.0)]);
         let result = find_negative_cycle(&graph, NodeIndex::new(0));
-        assert!(result.is_none());
+        assert_eq!(result, Ok(None));
     }
 }
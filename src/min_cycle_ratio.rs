@@ -0,0 +1,246 @@
+//! Minimum cycle ratio solver.
+//!
+//! Given a directed graph whose edges carry a cost and a time, the minimum cycle
+//! ratio problem asks for the cycle `C` minimizing `sum(cost) / sum(time)` over `C`.
+//! It is the core computation behind throughput analysis of synchronous dataflow
+//! graphs and is solved here with
+//! [`MaxParametricSolver`](crate::parametric::MaxParametricSolver) on the parametric
+//! distance `cost - ratio * time`.
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num::traits::{Inv, One, Zero};
+use petgraph::visit::{EdgeRef, IntoEdges, IntoNodeIdentifiers, NodeIndexable};
+
+use crate::parametric::{MaxParametricSolver, ParametricAPI};
+
+/// [`ParametricAPI`] for cost-to-time cycle ratios.
+///
+/// `cost` and `time` extract the two quantities from an edge weight.
+#[derive(Debug, Clone)]
+pub struct CycleRatioAPI<C, T> {
+    cost: C,
+    time: T,
+}
+
+impl<C, T> CycleRatioAPI<C, T> {
+    /// Creates a new [`CycleRatioAPI`] from the cost and time extractors.
+    pub fn new(cost: C, time: T) -> Self {
+        Self { cost, time }
+    }
+}
+
+impl<E, R, C, T> ParametricAPI<E, R> for CycleRatioAPI<C, T>
+where
+    E: EdgeRef,
+    R: Copy + PartialOrd + Zero + Sub<Output = R> + Mul<Output = R> + Div<Output = R>,
+    C: Fn(&E::Weight) -> R,
+    T: Fn(&E::Weight) -> R,
+{
+    fn distance(&self, ratio: &R, edge: &E) -> R {
+        (self.cost)(edge.weight()) - *ratio * (self.time)(edge.weight())
+    }
+
+    fn zero_cancel(&self, cycle: &[E]) -> R {
+        let mut total_cost = R::zero();
+        let mut total_time = R::zero();
+        for edge in cycle {
+            total_cost = total_cost + (self.cost)(edge.weight());
+            total_time = total_time + (self.time)(edge.weight());
+        }
+        total_cost / total_time
+    }
+}
+
+/// Result of [`MinCycleRatioSolver::run`].
+#[derive(Debug, Clone)]
+pub struct CycleRatioSolution<E, R> {
+    /// The minimum cycle ratio (or the initial ratio if no cycle beats it).
+    pub ratio: R,
+    /// The critical cycle attaining `ratio`; empty if no cycle beats the initial ratio.
+    pub cycle: Vec<E>,
+    /// Final node potentials, indexed by `NodeIndexable::to_index`. They satisfy
+    /// `potentials[v] <= potentials[u] + cost(u, v) - ratio * time(u, v)` for every edge.
+    pub potentials: Vec<R>,
+}
+
+/// Minimum cost-to-time cycle ratio solver.
+///
+/// By default edges carry `(cost, time)` pairs; use [`MinCycleRatioSolver::with_weights`]
+/// to supply the two quantities through closures instead. `G` is any graph reference
+/// accepted by [`MaxParametricSolver`], e.g. `&DiGraph` or `&StableGraph`.
+#[derive(Debug, Clone)]
+pub struct MinCycleRatioSolver<G, C, T> {
+    digraph: G,
+    cost: C,
+    time: T,
+}
+
+impl<G, R> MinCycleRatioSolver<G, fn(&(R, R)) -> R, fn(&(R, R)) -> R>
+where
+    G: IntoEdges<EdgeWeight = (R, R)>,
+    R: Copy,
+{
+    /// Creates a new [`MinCycleRatioSolver`] for a graph whose edges carry
+    /// `(cost, time)` pairs.
+    pub fn new(digraph: G) -> Self {
+        Self::with_weights(digraph, |w| w.0, |w| w.1)
+    }
+}
+
+impl<G, C, T> MinCycleRatioSolver<G, C, T>
+where
+    G: IntoEdges,
+{
+    /// Creates a new [`MinCycleRatioSolver`] taking the cost and time of each edge
+    /// from the `cost` and `time` closures.
+    pub fn with_weights<R>(digraph: G, cost: C, time: T) -> Self
+    where
+        C: Fn(&G::EdgeWeight) -> R,
+        T: Fn(&G::EdgeWeight) -> R,
+    {
+        Self {
+            digraph,
+            cost,
+            time,
+        }
+    }
+
+    /// The function `run` solves the minimum cycle ratio problem.
+    ///
+    /// Arguments:
+    ///
+    /// * `r0`: The initial ratio. It must be an upper bound of the answer (e.g. a ratio
+    ///   of some known cycle, or a large value); only cycles with a smaller ratio are
+    ///   reported.
+    ///
+    /// Returns:
+    ///
+    /// a [`CycleRatioSolution`] with the ratio, the critical cycle and the node potentials.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::graph::DiGraph;
+    /// use num::rational::Ratio;
+    /// use netoptim_rs::min_cycle_ratio::MinCycleRatioSolver;
+    ///
+    /// let r = |n| Ratio::new(n, 1);
+    /// let digraph = DiGraph::<(), (Ratio<i32>, Ratio<i32>)>::from_edges([
+    ///     (0, 1, (r(5), r(1))),
+    ///     (1, 0, (r(1), r(1))), // ratio 6/2 = 3
+    ///     (1, 2, (r(2), r(1))),
+    ///     (2, 1, (r(2), r(3))), // ratio 4/4 = 1
+    /// ]);
+    ///
+    /// let solver = MinCycleRatioSolver::new(&digraph);
+    /// let solution = solver.run(r(10));
+    /// assert_eq!(solution.ratio, r(1));
+    /// assert_eq!(solution.cycle.len(), 2);
+    /// ```
+    pub fn run<R>(&self, r0: R) -> CycleRatioSolution<G::EdgeRef, R>
+    where
+        G: IntoNodeIdentifiers + NodeIndexable,
+        R: Copy
+            + PartialOrd
+            + Zero
+            + One
+            + Add<Output = R>
+            + Sub<Output = R>
+            + Mul<Output = R>
+            + Div<Output = R>
+            + Neg<Output = R>
+            + Inv<Output = R>,
+        C: Fn(&G::EdgeWeight) -> R,
+        T: Fn(&G::EdgeWeight) -> R,
+    {
+        let omega = CycleRatioAPI::new(&self.cost, &self.time);
+        let mut solver = MaxParametricSolver::new(self.digraph, omega);
+        let mut potentials = vec![R::zero(); self.digraph.node_bound()];
+        let mut ratio = r0;
+        let cycle = solver.run(&mut potentials, &mut ratio);
+        CycleRatioSolution {
+            ratio,
+            cycle,
+            potentials,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::rational::Ratio;
+    use petgraph::graph::DiGraph;
+
+    fn r(n: i32) -> Ratio<i32> {
+        Ratio::new(n, 1)
+    }
+
+    #[test]
+    fn test_min_cycle_ratio_pairs() {
+        let digraph = DiGraph::<(), (Ratio<i32>, Ratio<i32>)>::from_edges([
+            (0, 1, (r(1), r(1))),
+            (1, 2, (r(1), r(1))),
+            (2, 0, (r(1), r(2))), // ratio 3/4
+            (0, 2, (r(4), r(1))),
+            (2, 1, (r(1), r(1))), // 1 -> 2 -> 1: ratio 2/2
+        ]);
+
+        let solver = MinCycleRatioSolver::new(&digraph);
+        let solution = solver.run(r(100));
+        assert_eq!(solution.ratio, Ratio::new(3, 4));
+        assert_eq!(solution.cycle.len(), 3);
+
+        // The potentials certify that no cycle has a smaller ratio.
+        for edge in digraph.edge_references() {
+            let (cost, time) = *edge.weight();
+            let (u, v) = (edge.source().index(), edge.target().index());
+            assert!(
+                solution.potentials[v] <= solution.potentials[u] + cost - solution.ratio * time
+            );
+        }
+    }
+
+    #[test]
+    fn test_min_cycle_ratio_closures() {
+        struct Channel {
+            delay: f64,
+            tokens: f64,
+        }
+
+        let mut digraph = DiGraph::<&str, Channel>::new();
+        let a = digraph.add_node("a");
+        let b = digraph.add_node("b");
+        digraph.add_edge(
+            a,
+            b,
+            Channel {
+                delay: 3.0,
+                tokens: 1.0,
+            },
+        );
+        digraph.add_edge(
+            b,
+            a,
+            Channel {
+                delay: 1.0,
+                tokens: 1.0,
+            },
+        );
+
+        let solver = MinCycleRatioSolver::with_weights(&digraph, |c| c.delay, |c| c.tokens);
+        let solution = solver.run(100.0);
+        assert_eq!(solution.ratio, 2.0);
+        assert_eq!(solution.cycle.len(), 2);
+    }
+
+    #[test]
+    fn test_min_cycle_ratio_acyclic() {
+        let digraph = DiGraph::<(), (Ratio<i32>, Ratio<i32>)>::from_edges([(0, 1, (r(1), r(1)))]);
+
+        let solver = MinCycleRatioSolver::new(&digraph);
+        let solution = solver.run(r(100));
+        assert!(solution.cycle.is_empty());
+        assert_eq!(solution.ratio, r(100));
+    }
+}
@@ -0,0 +1,293 @@
+//! Monotone integer priority queues.
+//!
+//! In Dijkstra's algorithm the popped keys never decrease, and every key pushed is at
+//! least the last one popped. Queues that rely on this, with `u64` keys, beat a binary
+//! heap when edge weights are small integers:
+//!
+//! * [`BucketQueue`](crate::monotone_queue::BucketQueue) is Dial's bucket queue: one
+//!   bucket per key, in a ring of about `C` buckets for a maximal edge weight `C`. Push
+//!   and pop are O(1) amortized, plus O(C) over the whole run for skipping empty buckets.
+//! * [`RadixHeap`](crate::monotone_queue::RadixHeap) keeps one bucket per bit of
+//!   difference from the last popped key. Each item moves to a lower bucket at most 64
+//!   times, whatever the weights.
+//!
+//! Both are used through [`dijkstra_with_queue`](crate::dijkstra::dijkstra_with_queue).
+
+use std::collections::VecDeque;
+
+/// A min-priority queue with `u64` keys that requires monotone use: a pushed key must
+/// not be smaller than the last popped key.
+///
+/// Items with equal keys are popped in an unspecified order.
+pub trait MonotoneQueue<T> {
+    /// Adds `item` with priority `key`.
+    ///
+    /// # Panics
+    /// If `key` is smaller than the last popped key.
+    fn push(&mut self, key: u64, item: T);
+
+    /// Removes an item with the smallest key, and returns it with its key.
+    fn pop(&mut self) -> Option<(u64, T)>;
+
+    /// The number of items in the queue.
+    fn len(&self) -> usize;
+
+    /// Whether the queue is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Dial's bucket queue.
+///
+/// The ring of buckets grows to the largest span between the smallest and largest key
+/// in the queue, so memory is O(C) for a maximal edge weight `C`: use it for small
+/// integer weights.
+///
+/// # Example
+/// ```rust
+/// use netoptim_rs::monotone_queue::{BucketQueue, MonotoneQueue};
+///
+/// let mut queue = BucketQueue::new();
+/// queue.push(3, 'c');
+/// queue.push(1, 'a');
+/// assert_eq!(queue.pop(), Some((1, 'a')));
+/// queue.push(2, 'b');
+/// assert_eq!(queue.pop(), Some((2, 'b')));
+/// assert_eq!(queue.pop(), Some((3, 'c')));
+/// assert_eq!(queue.pop(), None);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BucketQueue<T> {
+    // `buckets[k]` holds the items with key `base + k`.
+    buckets: VecDeque<Vec<T>>,
+    base: u64,
+    len: usize,
+}
+
+impl<T> BucketQueue<T> {
+    /// Creates an empty queue.
+    pub fn new() -> Self {
+        BucketQueue {
+            buckets: VecDeque::new(),
+            base: 0,
+            len: 0,
+        }
+    }
+}
+
+impl<T> Default for BucketQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> MonotoneQueue<T> for BucketQueue<T> {
+    fn push(&mut self, key: u64, item: T) {
+        assert!(key >= self.base, "monotone queue key decreased");
+        let k = (key - self.base) as usize;
+        if k >= self.buckets.len() {
+            self.buckets.resize_with(k + 1, Vec::new);
+        }
+        self.buckets[k].push(item);
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(u64, T)> {
+        if self.len == 0 {
+            return None;
+        }
+        loop {
+            let front = self.buckets.front_mut().expect("a bucket holds the items");
+            if let Some(item) = front.pop() {
+                self.len -= 1;
+                return Some((self.base, item));
+            }
+            // Rotate the empty bucket to the back, where it stands for the next key
+            // past the end, keeping its allocation.
+            let empty = self.buckets.pop_front().expect("the front bucket exists");
+            self.buckets.push_back(empty);
+            self.base += 1;
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+/// Radix heap.
+///
+/// Bucket `i > 0` holds the keys whose highest bit differing from the last popped key
+/// is bit `i - 1`, and bucket 0 the keys equal to it. Popping from an empty bucket 0
+/// redistributes the first non-empty bucket around its minimum.
+///
+/// # Example
+/// ```rust
+/// use netoptim_rs::monotone_queue::{MonotoneQueue, RadixHeap};
+///
+/// let mut queue = RadixHeap::new();
+/// queue.push(1_000_000, 'c');
+/// queue.push(7, 'a');
+/// queue.push(40_000, 'b');
+/// assert_eq!(queue.pop(), Some((7, 'a')));
+/// assert_eq!(queue.pop(), Some((40_000, 'b')));
+/// assert_eq!(queue.pop(), Some((1_000_000, 'c')));
+/// assert!(queue.is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RadixHeap<T> {
+    buckets: Vec<Vec<(u64, T)>>,
+    last: u64,
+    len: usize,
+}
+
+impl<T> RadixHeap<T> {
+    /// Creates an empty heap.
+    pub fn new() -> Self {
+        RadixHeap {
+            buckets: (0..=u64::BITS).map(|_| Vec::new()).collect(),
+            last: 0,
+            len: 0,
+        }
+    }
+
+    // Bucket of `key` relative to the last popped key.
+    #[inline(always)]
+    fn bucket(&self, key: u64) -> usize {
+        (u64::BITS - (key ^ self.last).leading_zeros()) as usize
+    }
+}
+
+impl<T> Default for RadixHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> MonotoneQueue<T> for RadixHeap<T> {
+    fn push(&mut self, key: u64, item: T) {
+        assert!(key >= self.last, "monotone queue key decreased");
+        let b = self.bucket(key);
+        self.buckets[b].push((key, item));
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(u64, T)> {
+        if self.len == 0 {
+            return None;
+        }
+        if self.buckets[0].is_empty() {
+            let i = (1..self.buckets.len())
+                .find(|&i| !self.buckets[i].is_empty())
+                .expect("a bucket holds the items");
+            let mut items = std::mem::take(&mut self.buckets[i]);
+            self.last = items.iter().map(|(key, _)| *key).min().expect("non-empty");
+            // Every item lands in a lower bucket, the minimum in bucket 0.
+            for (key, item) in items.drain(..) {
+                let b = self.bucket(key);
+                self.buckets[b].push((key, item));
+            }
+            // Keep the allocation for later redistributions.
+            self.buckets[i] = items;
+        }
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::Lcg;
+
+    // Interleaved pushes and pops, with every push at least the last popped key, must
+    // pop the keys in sorted order.
+    fn check_monotone<Q: MonotoneQueue<usize>>(mut queue: Q, max_step: u64) {
+        let mut rng = Lcg::new(0x853c_49e6);
+        let mut reference: Vec<u64> = Vec::new();
+        let mut last = 0;
+        for round in 0..2000 {
+            for _ in 0..rng.below(4) {
+                let key = last + rng.below(max_step);
+                queue.push(key, round);
+                reference.push(key);
+            }
+            reference.sort_unstable_by(|a, b| b.cmp(a));
+            assert_eq!(queue.len(), reference.len());
+            if rng.below(3) > 0 {
+                let popped = queue.pop().map(|(key, _)| key);
+                assert_eq!(popped, reference.pop());
+                last = popped.unwrap_or(last);
+            }
+        }
+        while let Some((key, _)) = queue.pop() {
+            assert_eq!(Some(key), reference.pop());
+        }
+        assert!(reference.is_empty());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_bucket_queue_pops_in_order() {
+        check_monotone(BucketQueue::new(), 10);
+    }
+
+    #[test]
+    fn test_radix_heap_pops_in_order() {
+        check_monotone(RadixHeap::new(), 10);
+        check_monotone(RadixHeap::new(), 1 << 40);
+    }
+
+    #[test]
+    fn test_radix_heap_extreme_keys() {
+        let mut queue = RadixHeap::new();
+        queue.push(u64::MAX, 2);
+        queue.push(0, 0);
+        queue.push(u64::MAX - 1, 1);
+        assert_eq!(queue.pop(), Some((0, 0)));
+        assert_eq!(queue.pop(), Some((u64::MAX - 1, 1)));
+        queue.push(u64::MAX, 3);
+        let mut rest: Vec<_> = std::iter::from_fn(|| queue.pop()).collect();
+        rest.sort();
+        assert_eq!(rest, vec![(u64::MAX, 2), (u64::MAX, 3)]);
+    }
+
+    #[test]
+    fn test_bucket_queue_reuses_buckets() {
+        // A sliding window of keys, as from edge weights of at most 5.
+        let mut queue = BucketQueue::new();
+        for key in 0..5 {
+            queue.push(key, key);
+        }
+        for key in 0..1000 {
+            queue.push(key + 5, key + 5);
+            assert_eq!(queue.pop(), Some((key, key)));
+        }
+        // The ring spans the last popped key and the 5 keys after it, nothing more.
+        assert!(queue.buckets.len() <= 7);
+        assert_eq!(queue.len(), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "key decreased")]
+    fn test_bucket_queue_rejects_decreasing_key() {
+        let mut queue = BucketQueue::new();
+        queue.push(5, ());
+        queue.pop();
+        queue.push(4, ());
+    }
+
+    #[test]
+    #[should_panic(expected = "key decreased")]
+    fn test_radix_heap_rejects_decreasing_key() {
+        let mut queue = RadixHeap::new();
+        queue.push(5, ());
+        queue.pop();
+        queue.push(4, ());
+    }
+}
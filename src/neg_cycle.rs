@@ -1,75 +1,75 @@
-use petgraph::graph::{EdgeReference, NodeIndex};
-use petgraph::prelude::*;
-use petgraph::visit::EdgeRef;
-use petgraph::visit::IntoNodeIdentifiers;
-
-// use petgraph::visit::IntoNeighborsDirected;
+use petgraph::visit::{EdgeRef, IntoEdges, IntoNodeIdentifiers, NodeIndexable};
 
 /// The `NegCycleFinder` struct is used to find negative cycles in a directed graph.
 ///
+/// It is generic over petgraph's visit traits, so it works on `DiGraph`, `StableGraph`,
+/// `GraphMap`, `Csr` or any user-defined adjacency structure implementing
+/// `IntoEdges + IntoNodeIdentifiers + NodeIndexable`. Distance slices passed to its
+/// methods are indexed by `NodeIndexable::to_index` and must be at least
+/// `node_bound()` long, which keeps graphs with index holes (e.g. a `StableGraph`
+/// after node removal) working.
+///
+/// The predecessor and visited arrays are dense `Vec`s sized by `node_bound()` and
+/// are kept between calls, so repeated `howard` calls on the same finder (as made by
+/// `MaxParametricSolver::run`) do not reallocate.
+///
 /// Properties:
 ///
-/// * `digraph`: The `digraph` property is a reference to a directed graph (`DiGraph`) that the
-///   `NegCycleFinder` is operating on. It is annotated with a lifetime `'a`, indicating that the
-///   reference is valid for a certain scope.
-/// * `pred`: The `pred` property is a `HashMap` that maps a `NodeIndex` to a tuple containing the
-///   previous node index and an `EdgeReference`. This is used to keep track of the predecessor node and
-///   the edge that leads to that node during the process of finding negative cycles in a directed graph
+/// * `digraph`: The `digraph` property is the graph reference (e.g. `&DiGraph<V, D>`) that the
+///   `NegCycleFinder` is operating on.
+/// * `pred`: The `pred` property is a `Vec` indexed by `NodeIndexable::to_index` holding, for each
+///   node, the previous node and the edge that leads to that node (or `None`). This is used to keep
+///   track of the predecessor node and the edge during the process of finding negative cycles.
+/// * `visited`: Scratch space for `find_cycle`, recording which root each node was reached from.
 #[derive(Debug, Clone)]
-pub struct NegCycleFinder<'a, V, D> {
-    pub digraph: &'a DiGraph<V, D>,
-    pub pred: std::collections::HashMap<NodeIndex, (NodeIndex, EdgeReference<'a, D>)>,
+pub struct NegCycleFinder<G>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    pub digraph: G,
+    pub pred: Vec<Option<(G::NodeId, G::EdgeRef)>>,
+    visited: Vec<Option<G::NodeId>>,
 }
 
-impl<'a, V, D> NegCycleFinder<'a, V, D>
+impl<G> NegCycleFinder<G>
 where
-    D: std::ops::Add<Output = D> + std::cmp::PartialOrd + Copy,
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
 {
-    /// The `new` function creates a new `NegCycleFinder` object with an empty predecessor map.
+    /// The `new` function creates a new `NegCycleFinder` object with an empty predecessor array.
     ///
     /// Arguments:
     ///
-    /// * `digraph`: A reference to a directed graph (`DiGraph`) that the `NegCycleFinder` will operate on.
+    /// * `digraph`: A graph reference (e.g. `&DiGraph<V, D>` or `&StableGraph<V, D>`) that the
+    ///   `NegCycleFinder` will operate on.
     ///
     /// Returns:
     ///
-    /// The `new` function is returning an instance of the `NegCycleFinder<V, D>` struct.
-    /// Creates a new [`NegCycleFinder<V, D>`].
-    pub fn new(digraph: &'a DiGraph<V, D>) -> Self {
+    /// The `new` function is returning an instance of the `NegCycleFinder<G>` struct.
+    /// Creates a new [`NegCycleFinder<G>`].
+    pub fn new(digraph: G) -> Self {
+        let bound = digraph.node_bound();
         Self {
             digraph,
-            pred: std::collections::HashMap::new(),
+            pred: vec![None; bound],
+            visited: vec![None; bound],
         }
     }
 
+    /// Clears the predecessor array, resizing it to `node_bound()` without giving
+    /// back its capacity.
+    fn reset(&mut self) {
+        let bound = self.digraph.node_bound();
+        self.pred.clear();
+        self.pred.resize(bound, None);
+    }
+
     /// The `find_cycle` function in Rust returns the first node in a cycle found in a directed graph.
     ///
     /// Returns:
     ///
-    /// The function `find_cycle` returns an `Option<NodeIndex>`.
-    pub fn find_cycle(&self) -> Option<NodeIndex> {
-        let mut visited = std::collections::HashMap::new();
-        for vtx in self.digraph.node_identifiers() {
-            if visited.contains_key(&vtx) {
-                continue;
-            }
-            let mut utx = vtx;
-            while !visited.contains_key(&utx) {
-                visited.insert(utx, vtx);
-                if !self.pred.contains_key(&utx) {
-                    break;
-                }
-                let result = *self.pred.get(&utx).unwrap();
-                utx = result.0;
-                if visited.contains_key(&utx) {
-                    if visited[&utx] == vtx {
-                        return Some(utx);
-                    }
-                    break;
-                }
-            }
-        }
-        None
+    /// The function `find_cycle` returns an `Option<G::NodeId>`.
+    pub fn find_cycle(&mut self) -> Option<G::NodeId> {
+        find_cycle_in(self.digraph, &self.pred, &mut self.visited)
     }
 
     /// The `relax` function updates the distances between nodes in a graph based on the weights of the
@@ -78,33 +78,33 @@ where
     /// Arguments:
     ///
     /// * `dist`: `dist` is a mutable reference to a slice of type `D`. It represents the distances from
-    ///   a source node to each node in a graph.
-    /// * `get_weight`: The `get_weight` parameter is a closure that takes an `EdgeReference<D>` as
-    ///   input and returns a value of type `D`. This closure is used to calculate the weight of each edge
-    ///   in the graph. The `EdgeReference<D>` represents a reference to an edge in the graph, and
+    ///   a source node to each node in a graph, indexed by `NodeIndexable::to_index`.
+    /// * `get_weight`: The `get_weight` parameter is a closure that takes an edge reference
+    ///   (`G::EdgeRef`) as input and returns a value of type `D`. This closure is used to calculate
+    ///   the weight of each edge in the graph.
     ///
     /// Returns:
     ///
     /// a boolean value.
-    pub fn relax<F>(&mut self, dist: &mut [D], get_weight: F) -> bool
+    pub fn relax<D, F>(&mut self, dist: &mut [D], get_weight: F) -> bool
     where
-        F: Fn(EdgeReference<D>) -> D,
+        D: std::ops::Add<Output = D> + std::cmp::PartialOrd + Copy,
+        F: Fn(G::EdgeRef) -> D,
     {
+        if self.pred.len() < self.digraph.node_bound() {
+            self.reset();
+        }
+        let g = self.digraph;
+        let ix = |i| g.to_index(i);
         let mut changed = false;
-        for utx in self.digraph.node_identifiers() {
-            for edge in self.digraph.edges(utx) {
+        for utx in g.node_identifiers() {
+            for edge in g.edges(utx) {
                 let vtx = edge.target();
                 let weight = get_weight(edge);
-                // for utx in self.digraph.node_indices() {
-                //     for vtx in self
-                //         .digraph
-                //         .neighbors_directed(utx, petgraph::Direction::Outgoing)
-                //     {
-                // let weight = get_weight((utx, vtx));
-                let distance = dist[utx.index()] + weight;
-                if dist[vtx.index()] > distance {
-                    dist[vtx.index()] = distance;
-                    self.pred.insert(vtx, (utx, edge));
+                let distance = dist[ix(utx)] + weight;
+                if dist[ix(vtx)] > distance {
+                    dist[ix(vtx)] = distance;
+                    self.pred[ix(vtx)] = Some((utx, edge));
                     changed = true;
                 }
             }
@@ -120,13 +120,13 @@ where
     /// * `dist`: `dist` is a mutable reference to an array of type `D`. This array is used to store the
     ///   distances from the source vertex to each vertex in the graph. The algorithm will update the
     ///   distances during the execution.
-    /// * `get_weight`: `get_weight` is a closure that takes an `EdgeReference<D>` and returns the
-    ///   weight of that edge. The `howard` function uses this closure to get the weight of each edge in
+    /// * `get_weight`: `get_weight` is a closure that takes an edge reference (`G::EdgeRef`) and
+    ///   returns the weight of that edge. The `howard` function uses this closure to get the weight of each edge in
     ///   the graph.
     ///
     /// Returns:
     ///
-    /// The `howard` function returns an `Option<Vec<EdgeReference<'a, D>>>`.
+    /// The `howard` function returns an `Option<Vec<G::EdgeRef>>`.
     /// Howard's algorithm for finding negative cycles
     ///
     /// # Examples
@@ -167,11 +167,12 @@ where
     /// let result = ncf.howard(&mut dist, |e| { *e.weight()});
     /// assert!(result.is_none());
     /// ```
-    pub fn howard<F>(&mut self, dist: &mut [D], get_weight: F) -> Option<Vec<EdgeReference<'a, D>>>
+    pub fn howard<D, F>(&mut self, dist: &mut [D], get_weight: F) -> Option<Vec<G::EdgeRef>>
     where
-        F: Fn(EdgeReference<D>) -> D,
+        D: std::ops::Add<Output = D> + std::cmp::PartialOrd + Copy,
+        F: Fn(G::EdgeRef) -> D,
     {
-        self.pred.clear();
+        self.reset();
         while self.relax(dist, &get_weight) {
             let v_opt = self.find_cycle();
             if let Some(vtx) = v_opt {
@@ -186,31 +187,79 @@ where
     ///
     /// Arguments:
     ///
-    /// * `handle`: The `handle` parameter is of type `NodeIndex`. It represents the starting node index
+    /// * `handle`: The `handle` parameter is of type `G::NodeId`. It represents the starting node index
     ///   from which the cycle traversal will begin.
     ///
     /// Returns:
     ///
     /// The function `cycle_list` returns a vector of `EdgeReference` objects.
-    fn cycle_list(&self, handle: NodeIndex) -> Vec<EdgeReference<'a, D>> {
-        let mut vtx = handle;
-        let mut cycle = Vec::new();
-        loop {
-            let (utx, edge) = self.pred[&vtx];
-            cycle.push(edge);
-            vtx = utx;
-            if vtx == handle {
+    fn cycle_list(&self, handle: G::NodeId) -> Vec<G::EdgeRef> {
+        cycle_list_in(self.digraph, &self.pred, handle)
+    }
+}
+
+/// Walks the `point_to` forest (predecessors or successors, indexed by
+/// `NodeIndexable::to_index`) and returns a node lying on a cycle, if any.
+///
+/// `visited` is caller-owned scratch space that is resized to `node_bound()`.
+pub(crate) fn find_cycle_in<G>(
+    g: G,
+    point_to: &[Option<(G::NodeId, G::EdgeRef)>],
+    visited: &mut Vec<Option<G::NodeId>>,
+) -> Option<G::NodeId>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    let ix = |i| g.to_index(i);
+    visited.clear();
+    visited.resize(g.node_bound(), None);
+    for vtx in g.node_identifiers() {
+        if visited[ix(vtx)].is_some() {
+            continue;
+        }
+        let mut utx = vtx;
+        visited[ix(utx)] = Some(vtx);
+        while let Some((next, _)) = point_to[ix(utx)] {
+            utx = next;
+            if let Some(root) = visited[ix(utx)] {
+                if root == vtx {
+                    return Some(utx);
+                }
                 break;
             }
+            visited[ix(utx)] = Some(vtx);
+        }
+    }
+    None
+}
+
+/// Collects the edges of the cycle through `handle` by following `point_to`.
+pub(crate) fn cycle_list_in<G>(
+    g: G,
+    point_to: &[Option<(G::NodeId, G::EdgeRef)>],
+    handle: G::NodeId,
+) -> Vec<G::EdgeRef>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    let mut vtx = handle;
+    let mut cycle = Vec::new();
+    loop {
+        let (next, edge) = point_to[g.to_index(vtx)].expect("every node on a cycle has a link");
+        cycle.push(edge);
+        vtx = next;
+        if vtx == handle {
+            break;
         }
-        cycle
     }
+    cycle
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use num::rational::Ratio;
+    use petgraph::prelude::*;
 
     #[test]
     fn it_works() {
@@ -363,4 +412,43 @@ mod tests {
         assert!(!expected_cycle_nodes.contains(&NodeIndex::new(3)));
         assert!(!expected_cycle_nodes.contains(&NodeIndex::new(4)));
     }
+
+    #[test]
+    fn test_neg_cycle_stable_graph_with_holes() {
+        use petgraph::stable_graph::StableDiGraph;
+        use petgraph::visit::NodeIndexable;
+
+        let mut graph = StableDiGraph::<(), Ratio<i32>>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        let c = graph.add_node(());
+        let d = graph.add_node(());
+        graph.add_edge(a, b, Ratio::new(1, 1));
+        graph.add_edge(b, d, Ratio::new(1, 1));
+        graph.add_edge(d, a, Ratio::new(-3, 1));
+        graph.add_edge(a, c, Ratio::new(1, 1));
+        graph.remove_node(c);
+
+        let mut ncf = NegCycleFinder::new(&graph);
+        let mut dist = vec![Ratio::new(0, 1); graph.node_bound()];
+        let result = ncf.howard(&mut dist, |e| *e.weight());
+        assert!(result.is_some());
+        let cycle = result.unwrap();
+        assert_eq!(cycle.len(), 3);
+        let cycle_weight: Ratio<i32> = cycle.iter().map(|e| *e.weight()).sum();
+        assert_eq!(cycle_weight, Ratio::new(-1, 1));
+    }
+
+    #[test]
+    fn test_neg_cycle_graph_map() {
+        use petgraph::graphmap::DiGraphMap;
+        use petgraph::visit::NodeIndexable;
+
+        let graph = DiGraphMap::<u32, i32>::from_edges([(10, 20, 1), (20, 30, 1), (30, 10, -3)]);
+        let mut ncf = NegCycleFinder::new(&graph);
+        let mut dist = vec![0; graph.node_bound()];
+        let result = ncf.howard(&mut dist, |e| *e.weight());
+        assert!(result.is_some());
+        assert_eq!(result.unwrap().len(), 3);
+    }
 }
@@ -0,0 +1,376 @@
+//! Constrained negative cycle detection.
+//!
+//! [`NegCycleFinderQ`](crate::neg_cycle_q::NegCycleFinderQ) mirrors `neg_cycle_q` from
+//! the original netoptim: besides the usual forward (predecessor) relaxation it offers a
+//! backward (successor) relaxation, and both only accept an update when a user supplied
+//! `update_ok(old, new)` predicate agrees. This allows some node potentials to be held
+//! fixed or kept within bounds, e.g. clock-skew problems where the arrival times of
+//! certain registers are pinned.
+
+use petgraph::visit::{EdgeRef, IntoEdges, IntoNodeIdentifiers, NodeIndexable};
+
+use crate::neg_cycle::{cycle_list_in, find_cycle_in};
+
+/// The `NegCycleFinderQ` struct is used to find negative cycles in a directed graph
+/// under an update constraint.
+///
+/// Like [`NegCycleFinder`](crate::neg_cycle::NegCycleFinder) it is generic over
+/// `IntoEdges + IntoNodeIdentifiers + NodeIndexable`, and distance slices are indexed by
+/// `NodeIndexable::to_index` and must be at least `node_bound()` long.
+///
+/// Properties:
+///
+/// * `digraph`: The graph reference that the `NegCycleFinderQ` is operating on.
+/// * `pred`: For each node, the previous node and the edge leading to it, as set by
+///   forward relaxation.
+/// * `succ`: For each node, the next node and the edge leaving it, as set by
+///   backward relaxation.
+#[derive(Debug, Clone)]
+pub struct NegCycleFinderQ<G>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    pub digraph: G,
+    pub pred: Vec<Option<(G::NodeId, G::EdgeRef)>>,
+    pub succ: Vec<Option<(G::NodeId, G::EdgeRef)>>,
+    visited: Vec<Option<G::NodeId>>,
+}
+
+impl<G> NegCycleFinderQ<G>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    /// Creates a new [`NegCycleFinderQ<G>`] with empty predecessor and successor arrays.
+    ///
+    /// Arguments:
+    ///
+    /// * `digraph`: A graph reference (e.g. `&DiGraph<V, D>`) that the finder will operate on.
+    pub fn new(digraph: G) -> Self {
+        let bound = digraph.node_bound();
+        Self {
+            digraph,
+            pred: vec![None; bound],
+            succ: vec![None; bound],
+            visited: vec![None; bound],
+        }
+    }
+
+    /// The `relax_pred` function performs one forward relaxation pass.
+    ///
+    /// For every edge `u -> v` with `dist[u] + w < dist[v]`, `dist[v]` is lowered to
+    /// `dist[u] + w` provided `update_ok(dist[v], dist[u] + w)` holds.
+    ///
+    /// Returns:
+    ///
+    /// `true` if any distance was changed.
+    pub fn relax_pred<D, F, U>(&mut self, dist: &mut [D], get_weight: F, update_ok: U) -> bool
+    where
+        D: std::ops::Add<Output = D> + std::cmp::PartialOrd + Copy,
+        F: Fn(G::EdgeRef) -> D,
+        U: Fn(D, D) -> bool,
+    {
+        if self.pred.len() < self.digraph.node_bound() {
+            reset(&mut self.pred, self.digraph.node_bound());
+        }
+        let g = self.digraph;
+        let ix = |i| g.to_index(i);
+        let mut changed = false;
+        for utx in g.node_identifiers() {
+            for edge in g.edges(utx) {
+                let vtx = edge.target();
+                let distance = dist[ix(utx)] + get_weight(edge);
+                if dist[ix(vtx)] > distance && update_ok(dist[ix(vtx)], distance) {
+                    dist[ix(vtx)] = distance;
+                    self.pred[ix(vtx)] = Some((utx, edge));
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+
+    /// The `relax_succ` function performs one backward relaxation pass.
+    ///
+    /// For every edge `u -> v` with `dist[v] - w > dist[u]`, `dist[u]` is raised to
+    /// `dist[v] - w` provided `update_ok(dist[u], dist[v] - w)` holds.
+    ///
+    /// Returns:
+    ///
+    /// `true` if any distance was changed.
+    pub fn relax_succ<D, F, U>(&mut self, dist: &mut [D], get_weight: F, update_ok: U) -> bool
+    where
+        D: std::ops::Sub<Output = D> + std::cmp::PartialOrd + Copy,
+        F: Fn(G::EdgeRef) -> D,
+        U: Fn(D, D) -> bool,
+    {
+        if self.succ.len() < self.digraph.node_bound() {
+            reset(&mut self.succ, self.digraph.node_bound());
+        }
+        let g = self.digraph;
+        let ix = |i| g.to_index(i);
+        let mut changed = false;
+        for utx in g.node_identifiers() {
+            for edge in g.edges(utx) {
+                let vtx = edge.target();
+                let distance = dist[ix(vtx)] - get_weight(edge);
+                if dist[ix(utx)] < distance && update_ok(dist[ix(utx)], distance) {
+                    dist[ix(utx)] = distance;
+                    self.succ[ix(utx)] = Some((vtx, edge));
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Constrained Howard's algorithm using forward (predecessor) relaxation.
+    ///
+    /// Arguments:
+    ///
+    /// * `dist`: The node potentials, updated in place.
+    /// * `get_weight`: A closure returning the weight of an edge.
+    /// * `update_ok`: A predicate `update_ok(old, new)` deciding whether a potential may
+    ///   be lowered from `old` to `new`.
+    ///
+    /// Returns:
+    ///
+    /// The edges of a negative cycle (in reverse order), or `None` if relaxation
+    /// converged without finding one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use petgraph::prelude::*;
+    /// use netoptim_rs::neg_cycle_q::NegCycleFinderQ;
+    ///
+    /// let digraph = DiGraph::<(), i32>::from_edges([(0, 1, 1), (1, 2, 1), (2, 0, -3)]);
+    /// let mut ncf = NegCycleFinderQ::new(&digraph);
+    /// let mut dist = [0, 0, 0];
+    /// let result = ncf.howard_pred(&mut dist, |e| *e.weight(), |_, _| true);
+    /// assert!(result.is_some());
+    ///
+    /// // Refusing every update keeps all potentials fixed, so no cycle is reported.
+    /// let mut dist = [0, 0, 0];
+    /// let result = ncf.howard_pred(&mut dist, |e| *e.weight(), |_, _| false);
+    /// assert!(result.is_none());
+    /// assert_eq!(dist, [0, 0, 0]);
+    /// ```
+    pub fn howard_pred<D, F, U>(
+        &mut self,
+        dist: &mut [D],
+        get_weight: F,
+        update_ok: U,
+    ) -> Option<Vec<G::EdgeRef>>
+    where
+        D: std::ops::Add<Output = D> + std::cmp::PartialOrd + Copy,
+        F: Fn(G::EdgeRef) -> D,
+        U: Fn(D, D) -> bool,
+    {
+        reset(&mut self.pred, self.digraph.node_bound());
+        while self.relax_pred(dist, &get_weight, &update_ok) {
+            if let Some(vtx) = find_cycle_in(self.digraph, &self.pred, &mut self.visited) {
+                return Some(cycle_list_in(self.digraph, &self.pred, vtx));
+            }
+        }
+        None
+    }
+
+    /// Constrained Howard's algorithm using backward (successor) relaxation.
+    ///
+    /// Arguments:
+    ///
+    /// * `dist`: The node potentials, updated in place.
+    /// * `get_weight`: A closure returning the weight of an edge.
+    /// * `update_ok`: A predicate `update_ok(old, new)` deciding whether a potential may
+    ///   be raised from `old` to `new`.
+    ///
+    /// Returns:
+    ///
+    /// The edges of a negative cycle (in forward order), or `None` if relaxation
+    /// converged without finding one.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use petgraph::prelude::*;
+    /// use netoptim_rs::neg_cycle_q::NegCycleFinderQ;
+    ///
+    /// let digraph = DiGraph::<(), i32>::from_edges([(0, 1, 1), (1, 2, 1), (2, 0, -3)]);
+    /// let mut ncf = NegCycleFinderQ::new(&digraph);
+    /// let mut dist = [0, 0, 0];
+    /// let cycle = ncf.howard_succ(&mut dist, |e| *e.weight(), |_, _| true).unwrap();
+    /// assert_eq!(cycle.iter().map(|e| *e.weight()).sum::<i32>(), -1);
+    /// ```
+    pub fn howard_succ<D, F, U>(
+        &mut self,
+        dist: &mut [D],
+        get_weight: F,
+        update_ok: U,
+    ) -> Option<Vec<G::EdgeRef>>
+    where
+        D: std::ops::Sub<Output = D> + std::cmp::PartialOrd + Copy,
+        F: Fn(G::EdgeRef) -> D,
+        U: Fn(D, D) -> bool,
+    {
+        reset(&mut self.succ, self.digraph.node_bound());
+        while self.relax_succ(dist, &get_weight, &update_ok) {
+            if let Some(vtx) = find_cycle_in(self.digraph, &self.succ, &mut self.visited) {
+                return Some(cycle_list_in(self.digraph, &self.succ, vtx));
+            }
+        }
+        None
+    }
+}
+
+/// Clears a link array, resizing it to `bound` without giving back its capacity.
+fn reset<T: Clone>(point_to: &mut Vec<Option<T>>, bound: usize) {
+    point_to.clear();
+    point_to.resize(bound, None);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num::rational::Ratio;
+    use petgraph::prelude::*;
+
+    fn neg_cycle_graph() -> DiGraph<(), Ratio<i32>> {
+        DiGraph::<(), Ratio<i32>>::from_edges([
+            (0, 1, Ratio::new(1, 1)),
+            (0, 2, Ratio::new(1, 1)),
+            (0, 3, Ratio::new(1, 1)),
+            (1, 3, Ratio::new(1, 1)),
+            (2, 1, Ratio::new(1, 1)),
+            (3, 2, Ratio::new(-3, 1)),
+        ])
+    }
+
+    #[test]
+    fn test_howard_pred_unconstrained() {
+        let digraph = neg_cycle_graph();
+        let mut ncf = NegCycleFinderQ::new(&digraph);
+        let mut dist = [Ratio::new(0, 1); 4];
+        let cycle = ncf
+            .howard_pred(&mut dist, |e| *e.weight(), |_, _| true)
+            .unwrap();
+        let cycle_weight: Ratio<i32> = cycle.iter().map(|e| *e.weight()).sum();
+        assert_eq!(cycle_weight, Ratio::new(-1, 1));
+    }
+
+    #[test]
+    fn test_howard_succ_unconstrained() {
+        let digraph = neg_cycle_graph();
+        let mut ncf = NegCycleFinderQ::new(&digraph);
+        let mut dist = [Ratio::new(0, 1); 4];
+        let cycle = ncf
+            .howard_succ(&mut dist, |e| *e.weight(), |_, _| true)
+            .unwrap();
+        let cycle_weight: Ratio<i32> = cycle.iter().map(|e| *e.weight()).sum();
+        assert_eq!(cycle_weight, Ratio::new(-1, 1));
+        // Successor cycles are reported in forward order.
+        for k in 0..cycle.len() {
+            assert_eq!(cycle[k].target(), cycle[(k + 1) % cycle.len()].source());
+        }
+    }
+
+    #[test]
+    fn test_howard_pred_no_cycle_feasible() {
+        let digraph = DiGraph::<(), i32>::from_edges([(0, 1, 2), (1, 2, -1), (2, 0, 1)]);
+        let mut ncf = NegCycleFinderQ::new(&digraph);
+        let mut dist = [0, 0, 0];
+        assert!(ncf
+            .howard_pred(&mut dist, |e| *e.weight(), |_, _| true)
+            .is_none());
+        for edge in digraph.edge_references() {
+            let (u, v) = (edge.source().index(), edge.target().index());
+            assert!(dist[v] <= dist[u] + *edge.weight());
+        }
+    }
+
+    #[test]
+    fn test_howard_succ_no_cycle_feasible() {
+        let digraph = DiGraph::<(), i32>::from_edges([(0, 1, 2), (1, 2, -1), (2, 0, 1)]);
+        let mut ncf = NegCycleFinderQ::new(&digraph);
+        let mut dist = [0, 0, 0];
+        assert!(ncf
+            .howard_succ(&mut dist, |e| *e.weight(), |_, _| true)
+            .is_none());
+        for edge in digraph.edge_references() {
+            let (u, v) = (edge.source().index(), edge.target().index());
+            assert!(dist[v] <= dist[u] + *edge.weight());
+        }
+    }
+
+    #[test]
+    fn test_howard_pred_bounded_update() {
+        // Potentials may not drop below -1, so the cycle cannot be exposed.
+        let digraph = DiGraph::<(), i32>::from_edges([(0, 1, 1), (1, 2, 1), (2, 0, -3)]);
+        let mut ncf = NegCycleFinderQ::new(&digraph);
+        let mut dist = [0, 0, 0];
+        let result = ncf.howard_pred(&mut dist, |e| *e.weight(), |_, new| new >= -1);
+        assert!(result.is_none());
+        assert!(dist.iter().all(|&d| d >= -1));
+    }
+
+    #[test]
+    fn test_howard_succ_bounded_update() {
+        // Potentials may not rise above 1, so the cycle cannot be exposed.
+        let digraph = DiGraph::<(), i32>::from_edges([(0, 1, 1), (1, 2, 1), (2, 0, -3)]);
+        let mut ncf = NegCycleFinderQ::new(&digraph);
+        let mut dist = [0, 0, 0];
+        let result = ncf.howard_succ(&mut dist, |e| *e.weight(), |_, new| new <= 1);
+        assert!(result.is_none());
+        assert!(dist.iter().all(|&d| d <= 1));
+    }
+
+    #[test]
+    fn test_howard_pred_pinned_node() {
+        // The only negative cycle, 0 -> 1 -> 2 -> 3 -> 0, needs node 0 to move. Node 0 is
+        // pinned at 0 and every other potential starts above it and stays positive, so
+        // `old != 0` rejects exactly the updates of node 0.
+        let digraph = DiGraph::<(), i32>::from_edges([
+            (0, 1, 2),
+            (1, 2, 1),
+            (0, 2, 5),
+            (2, 3, -1),
+            (1, 3, 4),
+            (3, 0, -4),
+        ]);
+        let mut ncf = NegCycleFinderQ::new(&digraph);
+        let mut dist = [0, 100, 100, 100];
+        assert!(ncf
+            .howard_pred(&mut dist, |e| *e.weight(), |_, _| true)
+            .is_some());
+
+        let mut dist = [0, 100, 100, 100];
+        let result = ncf.howard_pred(&mut dist, |e| *e.weight(), |old, _| old != 0);
+        assert!(result.is_none());
+        // Node 0 keeps its value; the others reach their shortest distances from it.
+        assert_eq!(dist, [0, 2, 3, 2]);
+    }
+
+    #[test]
+    fn test_howard_succ_pinned_node() {
+        // The mirror image: the only negative cycle, 0 -> 3 -> 2 -> 1 -> 0, needs node 0 to
+        // rise. Every other potential starts below 0 and stays negative.
+        let digraph = DiGraph::<(), i32>::from_edges([
+            (1, 0, 2),
+            (2, 1, 1),
+            (2, 0, 5),
+            (3, 2, -1),
+            (3, 1, 4),
+            (0, 3, -4),
+        ]);
+        let mut ncf = NegCycleFinderQ::new(&digraph);
+        let mut dist = [0, -100, -100, -100];
+        assert!(ncf
+            .howard_succ(&mut dist, |e| *e.weight(), |_, _| true)
+            .is_some());
+
+        let mut dist = [0, -100, -100, -100];
+        let result = ncf.howard_succ(&mut dist, |e| *e.weight(), |old, _| old != 0);
+        assert!(result.is_none());
+        // Node 0 keeps its value; the others reach minus their shortest distances to it.
+        assert_eq!(dist, [0, -2, -3, -2]);
+    }
+}
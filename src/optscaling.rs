@@ -0,0 +1,249 @@
+//! Optimal diagonal scaling of sparse matrices.
+//!
+//! Given a sparse matrix `A`, find positive row scalings `r` and column scalings `s`
+//! minimizing the ratio between the largest and the smallest magnitude of the
+//! nonzero entries of `diag(r) · A · diag(s)`.
+//!
+//! Taking logarithms `c_ij = ln|a_ij|`, `x_i = ln r_i` and `y_j = -ln s_j`, the
+//! scaled entries lie in `[1, e^γ]` exactly when `0 <= c_ij + x_i - y_j <= γ`. These
+//! are difference constraints on the bipartite row/column graph:
+//!
+//! * `row i -> col j` with weight `c_ij`, and
+//! * `col j -> row i` with weight `γ - c_ij`,
+//!
+//! feasible iff no negative cycle exists. The smallest such `γ` is found with
+//! [`MinParametricSolver`](crate::parametric::MinParametricSolver), and the node
+//! potentials give the scalings.
+
+use petgraph::graph::{DiGraph, NodeIndex};
+use petgraph::visit::EdgeRef;
+
+use crate::error::NetOptimError;
+use crate::parametric::{MinParametricSolver, ParametricAPI};
+
+/// Result of [`optimal_scaling`].
+#[derive(Debug, Clone)]
+pub struct MatrixScaling {
+    /// The row scaling factors `r_i`.
+    pub row_scaling: Vec<f64>,
+    /// The column scaling factors `s_j`.
+    pub col_scaling: Vec<f64>,
+    /// The achieved ratio `max|r_i a_ij s_j| / min|r_i a_ij s_j|` over the nonzero entries.
+    pub ratio: f64,
+}
+
+/// Edge weight of the row/column constraint graph.
+#[derive(Debug, Clone, Copy)]
+struct ScalingArc {
+    log_value: f64,
+    upper: bool,
+}
+
+struct ScalingAPI;
+
+impl<E: EdgeRef<Weight = ScalingArc>> ParametricAPI<E, f64> for ScalingAPI {
+    fn distance(&self, ratio: &f64, edge: &E) -> f64 {
+        let arc = edge.weight();
+        if arc.upper {
+            *ratio - arc.log_value
+        } else {
+            arc.log_value
+        }
+    }
+
+    fn zero_cancel(&self, cycle: &[E]) -> f64 {
+        let mut total = 0.0;
+        let mut count = 0.0;
+        for edge in cycle {
+            let arc = edge.weight();
+            if arc.upper {
+                total += arc.log_value;
+                count += 1.0;
+            } else {
+                total -= arc.log_value;
+            }
+        }
+        total / count
+    }
+}
+
+/// Computes an optimal diagonal scaling of a sparse matrix.
+///
+/// Arguments:
+///
+/// * `nrows`, `ncols`: The dimensions of the matrix.
+/// * `entries`: The matrix in triplet form `(row, col, value)`. Zero entries are ignored.
+///
+/// Returns:
+///
+/// The row and column scalings and the achieved max/min ratio of the scaled entries, or
+/// `NetOptimError::AlgorithmError` naming the offending row, column or entry if a triplet
+/// lies outside the matrix or its value is NaN or infinite.
+///
+/// # Example
+/// ```rust
+/// use netoptim_rs::optscaling::optimal_scaling;
+///
+/// // [[1, 10], [10, 1]] cannot be scaled below a ratio of 10.
+/// let entries = [(0, 0, 1.0), (0, 1, 10.0), (1, 0, 10.0), (1, 1, 1.0)];
+/// let scaling = optimal_scaling(2, 2, &entries).unwrap();
+/// assert!((scaling.ratio - 10.0).abs() < 1e-9);
+/// ```
+pub fn optimal_scaling(
+    nrows: usize,
+    ncols: usize,
+    entries: &[(usize, usize, f64)],
+) -> Result<MatrixScaling, NetOptimError> {
+    let mut digraph = DiGraph::<(), ScalingArc>::with_capacity(nrows + ncols, 2 * entries.len());
+    for _ in 0..nrows + ncols {
+        digraph.add_node(());
+    }
+    for &(i, j, value) in entries {
+        if i >= nrows {
+            return Err(NetOptimError::AlgorithmError(format!(
+                "row {} out of range for {} rows",
+                i, nrows
+            )));
+        }
+        if j >= ncols {
+            return Err(NetOptimError::AlgorithmError(format!(
+                "column {} out of range for {} columns",
+                j, ncols
+            )));
+        }
+        if !value.is_finite() {
+            return Err(NetOptimError::AlgorithmError(format!(
+                "entry ({}, {}) is not finite",
+                i, j
+            )));
+        }
+        if value == 0.0 {
+            continue;
+        }
+        let log_value = value.abs().ln();
+        let row = NodeIndex::new(i);
+        let col = NodeIndex::new(nrows + j);
+        digraph.add_edge(
+            row,
+            col,
+            ScalingArc {
+                log_value,
+                upper: false,
+            },
+        );
+        digraph.add_edge(
+            col,
+            row,
+            ScalingArc {
+                log_value,
+                upper: true,
+            },
+        );
+    }
+
+    let mut solver = MinParametricSolver::new(&digraph, ScalingAPI);
+    let mut dist = vec![0.0; nrows + ncols];
+    let mut gamma = 0.0;
+    solver.run(&mut dist, &mut gamma);
+
+    let row_scaling: Vec<f64> = dist[..nrows].iter().map(|x| x.exp()).collect();
+    let col_scaling: Vec<f64> = dist[nrows..].iter().map(|y| (-y).exp()).collect();
+
+    let mut lo = f64::INFINITY;
+    let mut hi = 0.0_f64;
+    for &(i, j, value) in entries {
+        if value == 0.0 {
+            continue;
+        }
+        let scaled = value.abs() * row_scaling[i] * col_scaling[j];
+        lo = lo.min(scaled);
+        hi = hi.max(scaled);
+    }
+    let ratio = if hi > 0.0 { hi / lo } else { 1.0 };
+
+    Ok(MatrixScaling {
+        row_scaling,
+        col_scaling,
+        ratio,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optscaling_rank_one() {
+        // a_ij = u_i * v_j scales to all ones.
+        let u = [1.0, 100.0, 0.01];
+        let v = [5.0, 0.2];
+        let mut entries = Vec::new();
+        for (i, ui) in u.iter().enumerate() {
+            for (j, vj) in v.iter().enumerate() {
+                entries.push((i, j, ui * vj));
+            }
+        }
+        let scaling = optimal_scaling(3, 2, &entries).unwrap();
+        assert!((scaling.ratio - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_optscaling_two_by_two() {
+        let entries = [(0, 0, 1.0), (0, 1, -10.0), (1, 0, 10.0), (1, 1, 1.0)];
+        let scaling = optimal_scaling(2, 2, &entries).unwrap();
+        assert!((scaling.ratio - 10.0).abs() < 1e-9);
+        assert_eq!(scaling.row_scaling.len(), 2);
+        assert_eq!(scaling.col_scaling.len(), 2);
+    }
+
+    #[test]
+    fn test_optscaling_improves_ratio() {
+        let entries = [
+            (0, 0, 1e-3),
+            (0, 2, 4.0),
+            (1, 1, 250.0),
+            (1, 2, 1e2),
+            (2, 0, 3.0),
+            (2, 1, 1e-1),
+            (3, 2, 7e4),
+        ];
+        let scaling = optimal_scaling(4, 3, &entries).unwrap();
+        let unscaled = 7e4 / 1e-3;
+        assert!(scaling.ratio >= 1.0);
+        assert!(scaling.ratio < unscaled);
+    }
+
+    #[test]
+    fn test_optscaling_invalid_index() {
+        let error = |msg: &str| NetOptimError::AlgorithmError(msg.to_string());
+        let entries = [(0, 0, 1.0), (2, 0, 1.0)];
+        assert_eq!(
+            optimal_scaling(2, 3, &entries).unwrap_err(),
+            error("row 2 out of range for 2 rows")
+        );
+        let entries = [(0, 0, 1.0), (0, 2, 1.0)];
+        assert_eq!(
+            optimal_scaling(3, 2, &entries).unwrap_err(),
+            error("column 2 out of range for 2 columns")
+        );
+    }
+
+    #[test]
+    fn test_optscaling_non_finite_entry() {
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            let entries = [(0, 0, 1.0), (1, 0, value)];
+            assert_eq!(
+                optimal_scaling(2, 2, &entries).unwrap_err(),
+                NetOptimError::AlgorithmError("entry (1, 0) is not finite".to_string())
+            );
+        }
+    }
+
+    #[test]
+    fn test_optscaling_empty() {
+        let scaling = optimal_scaling(2, 3, &[]).unwrap();
+        assert_eq!(scaling.ratio, 1.0);
+        assert_eq!(scaling.row_scaling, vec![1.0, 1.0]);
+        assert_eq!(scaling.col_scaling, vec![1.0, 1.0, 1.0]);
+    }
+}
@@ -1,16 +1,15 @@
 // use std::collections::HashMap;
 // use std::cmp::Ordering;
-use std::hash::Hash;
+use std::fmt;
+use std::marker::PhantomData;
 use std::ops::Add;
 use std::ops::Div;
 use std::ops::Mul;
 use std::ops::Neg;
 use std::ops::Sub;
 
-use petgraph::graph::{DiGraph, EdgeReference};
+use petgraph::visit::{IntoEdges, IntoNodeIdentifiers, NodeIndexable};
 
-// use petgraph::visit::EdgeRef;
-// use petgraph::visit::IntoNodeIdentifiers;
 // use petgraph::Direction;
 
 // use num::traits::Float;
@@ -19,44 +18,73 @@ use num::traits::One;
 use num::traits::Zero;
 
 use crate::neg_cycle::NegCycleFinder;
+use crate::neg_cycle_q::NegCycleFinderQ;
 
 /// API trait for parametric shortest path problems.
 ///
 /// Implement this trait to define how distances are computed and how
-/// to find the ratio that cancels a cycle.
+/// to find the ratio that cancels a cycle. `E` is the edge reference type of the
+/// graph, e.g. `EdgeReference<'a, W>` for a `&'a DiGraph<V, W>`; implementing it for
+/// every `E: EdgeRef` with a suitable `E::Weight` makes the API usable with any graph.
+///
+/// # Example
+/// ```rust
+/// use petgraph::visit::EdgeRef;
+/// use netoptim_rs::parametric::ParametricAPI;
+///
+/// // Mean cycle weight.
+/// struct MeanAPI;
+///
+/// impl<E: EdgeRef<Weight = f64>> ParametricAPI<E, f64> for MeanAPI {
+///     fn distance(&self, ratio: &f64, edge: &E) -> f64 {
+///         *edge.weight() - *ratio
+///     }
+///
+///     fn zero_cancel(&self, cycle: &[E]) -> f64 {
+///         cycle.iter().map(|e| *e.weight()).sum::<f64>() / cycle.len() as f64
+///     }
+/// }
+/// ```
 pub trait ParametricAPI<E, R>
 where
     R: Copy + PartialOrd,
-    E: Clone,
 {
-    fn distance(&self, ratio: &R, edge: &EdgeReference<R>) -> R;
-    fn zero_cancel(&self, cycle: &[EdgeReference<R>]) -> R;
+    fn distance(&self, ratio: &R, edge: &E) -> R;
+    fn zero_cancel(&self, cycle: &[E]) -> R;
 }
 
 /// Maximum parametric shortest path solver.
 ///
 /// Finds the minimum ratio cycle in a directed graph using Howard's algorithm
-/// for negative cycle detection.
-#[derive(Debug)]
-pub struct MaxParametricSolver<'a, V, R, P>
+/// for negative cycle detection. Like [`NegCycleFinder`], it works on any graph
+/// reference implementing `IntoEdges + IntoNodeIdentifiers + NodeIndexable`, such as
+/// `&DiGraph` or `&StableGraph`.
+pub struct MaxParametricSolver<G, R, P>
 where
-    R: Copy
-        + PartialOrd
-        + Add<Output = R>
-        + Sub<Output = R>
-        + Mul<Output = R>
-        + Div<Output = R>
-        + Neg<Output = R>
-        + Inv<Output = R>,
-    V: Eq + Hash + Clone,
-    P: ParametricAPI<V, R>,
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
 {
-    ncf: NegCycleFinder<'a, V, R>,
+    ncf: NegCycleFinder<G>,
     omega: P,
+    _ratio: PhantomData<R>,
+}
+
+impl<G, R, P> fmt::Debug for MaxParametricSolver<G, R, P>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    NegCycleFinder<G>: fmt::Debug,
+    P: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MaxParametricSolver")
+            .field("ncf", &self.ncf)
+            .field("omega", &self.omega)
+            .finish()
+    }
 }
 
-impl<'a, V, R, P> MaxParametricSolver<'a, V, R, P>
+impl<G, R, P> MaxParametricSolver<G, R, P>
 where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
     R: Copy
         + PartialOrd
         + Zero
@@ -67,15 +95,14 @@ where
         + Div<Output = R>
         + Neg<Output = R>
         + Inv<Output = R>,
-    V: Eq + Hash + Clone,
-    P: ParametricAPI<V, R>,
+    P: ParametricAPI<G::EdgeRef, R>,
 {
     /// The function creates a new instance of a struct with a given directed graph and a value.
     ///
     /// Arguments:
     ///
-    /// * `grph`: The `grph` parameter is a reference to a directed graph (`DiGraph`) with vertices of
-    ///   type `V` and edges of type `R`.
+    /// * `grph`: The `grph` parameter is a graph reference (e.g. `&DiGraph<V, W>` or
+    ///   `&StableGraph<V, W>`) whose edges are passed to `omega`.
     /// * `omega`: The `omega` parameter is of type `P`. It represents some value or parameter that is
     ///   used in the implementation of the `new` function. The specific meaning or purpose of `omega`
     ///   would depend on the context and the code that uses this function.
@@ -83,10 +110,11 @@ where
     /// Returns:
     ///
     /// The `new` function is returning an instance of the struct that it is defined in.
-    pub fn new(grph: &'a DiGraph<V, R>, omega: P) -> Self {
+    pub fn new(grph: G, omega: P) -> Self {
         Self {
             ncf: NegCycleFinder::new(grph),
             omega,
+            _ratio: PhantomData,
         }
     }
 
@@ -95,30 +123,30 @@ where
     /// Arguments:
     ///
     /// * `dist`: `dist` is a mutable reference to a slice of type `R`. It represents a distance matrix
-    ///   or array, where `R` is the type of the elements in the matrix.
+    ///   or array, where `R` is the type of the elements in the matrix. It is indexed by
+    ///   `NodeIndexable::to_index` and must be at least `node_bound()` long.
     /// * `ratio`: The `ratio` parameter is a mutable reference to a value of type `R`. It represents
     ///   the current ratio value that is being used in the algorithm. The algorithm will update this
     ///   value if it finds a smaller ratio during its execution.
     ///
     /// Returns:
     ///
-    /// a vector of `EdgeReference<R>`.
+    /// a vector of the graph's edge references (`G::EdgeRef`).
     /// # Example
     /// ```rust
     /// use petgraph::graph::DiGraph;
     /// use petgraph::prelude::*;
     /// use num::rational::Ratio;
     /// use netoptim_rs::parametric::{MaxParametricSolver, ParametricAPI};
-    /// use petgraph::graph::EdgeReference;
     ///
     /// struct TestParametricAPI;
     ///
-    /// impl ParametricAPI<(), Ratio<i32>> for TestParametricAPI {
-    ///     fn distance(&self, ratio: &Ratio<i32>, edge: &EdgeReference<Ratio<i32>>) -> Ratio<i32> {
+    /// impl<E: EdgeRef<Weight = Ratio<i32>>> ParametricAPI<E, Ratio<i32>> for TestParametricAPI {
+    ///     fn distance(&self, ratio: &Ratio<i32>, edge: &E) -> Ratio<i32> {
     ///         *edge.weight() - *ratio
     ///     }
     ///
-    ///     fn zero_cancel(&self, cycle: &[EdgeReference<Ratio<i32>>]) -> Ratio<i32> {
+    ///     fn zero_cancel(&self, cycle: &[E]) -> Ratio<i32> {
     ///         let mut sum_a = Ratio::new(0, 1);
     ///         let mut sum_b = Ratio::new(0, 1);
     ///         for edge in cycle {
@@ -143,10 +171,10 @@ where
     /// assert!(!cycle.is_empty());
     /// assert_eq!(ratio, Ratio::new(-1, 3));
     /// ```
-    pub fn run(&mut self, dist: &mut [R], ratio: &mut R) -> Vec<EdgeReference<'a, R>> {
+    pub fn run(&mut self, dist: &mut [R], ratio: &mut R) -> Vec<G::EdgeRef> {
         let mut r_min = *ratio;
-        let mut c_min = Vec::<EdgeReference<R>>::new();
-        let mut cycle = Vec::<EdgeReference<R>>::new();
+        let mut c_min = Vec::<G::EdgeRef>::new();
+        let mut cycle = Vec::<G::EdgeRef>::new();
         loop {
             if let Some(ci) = self.ncf.howard(dist, |e| self.omega.distance(ratio, &e)) {
                 let ri = self.omega.zero_cancel(&ci);
@@ -165,21 +193,170 @@ where
     }
 }
 
+/// Minimum parametric shortest path solver.
+///
+/// The counterpart of [`MaxParametricSolver`]: the distance returned by
+/// [`ParametricAPI::distance`] is expected to grow with the ratio, and the ratio is
+/// increased until no negative cycle remains. Cycles are searched with alternating
+/// backward (successor) and forward (predecessor) relaxation, as `min_parametric_q`
+/// does in the original netoptim. It works on the same graphs as
+/// [`MaxParametricSolver`].
+pub struct MinParametricSolver<G, R, P>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    ncf: NegCycleFinderQ<G>,
+    omega: P,
+    _ratio: PhantomData<R>,
+}
+
+impl<G, R, P> fmt::Debug for MinParametricSolver<G, R, P>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    NegCycleFinderQ<G>: fmt::Debug,
+    P: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MinParametricSolver")
+            .field("ncf", &self.ncf)
+            .field("omega", &self.omega)
+            .finish()
+    }
+}
+
+impl<G, R, P> MinParametricSolver<G, R, P>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+    R: Copy
+        + PartialOrd
+        + Zero
+        + One
+        + Add<Output = R>
+        + Sub<Output = R>
+        + Mul<Output = R>
+        + Div<Output = R>
+        + Neg<Output = R>
+        + Inv<Output = R>,
+    P: ParametricAPI<G::EdgeRef, R>,
+{
+    /// Creates a new [`MinParametricSolver`] for the directed graph `grph`, using `omega`
+    /// to compute parametric edge distances and cycle-cancelling ratios.
+    pub fn new(grph: G, omega: P) -> Self {
+        Self {
+            ncf: NegCycleFinderQ::new(grph),
+            omega,
+            _ratio: PhantomData,
+        }
+    }
+
+    /// The function `run` finds the maximum ratio and corresponding cycle in a given graph.
+    ///
+    /// Arguments:
+    ///
+    /// * `dist`: `dist` is a mutable reference to a slice of type `R` holding the node
+    ///   potentials, indexed by `NodeIndexable::to_index`.
+    /// * `ratio`: The `ratio` parameter is a mutable reference to the starting ratio. The
+    ///   algorithm will increase this value while it finds a larger cancelling ratio.
+    ///
+    /// Returns:
+    ///
+    /// a vector of edge references (`G::EdgeRef`) forming the critical cycle, or an empty
+    /// vector if the starting ratio was already feasible.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::graph::DiGraph;
+    /// use petgraph::visit::EdgeRef;
+    /// use num::rational::Ratio;
+    /// use netoptim_rs::parametric::{MinParametricSolver, ParametricAPI};
+    ///
+    /// struct MaxMeanAPI;
+    ///
+    /// impl<E: EdgeRef<Weight = Ratio<i32>>> ParametricAPI<E, Ratio<i32>> for MaxMeanAPI {
+    ///     fn distance(&self, ratio: &Ratio<i32>, edge: &E) -> Ratio<i32> {
+    ///         *ratio - *edge.weight()
+    ///     }
+    ///
+    ///     fn zero_cancel(&self, cycle: &[E]) -> Ratio<i32> {
+    ///         let sum_a: Ratio<i32> = cycle.iter().map(|e| *e.weight()).sum();
+    ///         sum_a / Ratio::new(cycle.len() as i32, 1)
+    ///     }
+    /// }
+    ///
+    /// let digraph = DiGraph::<(), Ratio<i32>>::from_edges(&[
+    ///     (0, 1, Ratio::new(1, 1)),
+    ///     (1, 2, Ratio::new(1, 1)),
+    ///     (2, 0, Ratio::new(3, 1)),
+    /// ]);
+    ///
+    /// let mut solver = MinParametricSolver::new(&digraph, MaxMeanAPI);
+    /// let mut dist = [Ratio::new(0, 1); 3];
+    /// let mut ratio = Ratio::new(0, 1);
+    ///
+    /// let cycle = solver.run(&mut dist, &mut ratio);
+    /// assert!(!cycle.is_empty());
+    /// assert_eq!(ratio, Ratio::new(5, 3));
+    /// ```
+    pub fn run(&mut self, dist: &mut [R], ratio: &mut R) -> Vec<G::EdgeRef> {
+        self.run_q(dist, ratio, |_, _| true)
+    }
+
+    /// Like [`run`](Self::run), but a potential only changes when `update_ok(old, new)`
+    /// holds, as in [`NegCycleFinderQ`]. This keeps pinned nodes at their starting
+    /// potential, e.g. registers whose clock arrival time is fixed.
+    ///
+    /// Cycles through a node whose potential never changes are not found, so the
+    /// returned ratio is the largest one among the cycles avoiding the pinned nodes.
+    pub fn run_q<U>(&mut self, dist: &mut [R], ratio: &mut R, update_ok: U) -> Vec<G::EdgeRef>
+    where
+        U: Fn(R, R) -> bool,
+    {
+        let mut r_max = *ratio;
+        let mut c_max = Vec::<G::EdgeRef>::new();
+        let mut cycle = Vec::<G::EdgeRef>::new();
+        let mut reverse = true;
+        loop {
+            let ci_opt = if reverse {
+                self.ncf
+                    .howard_succ(dist, |e| self.omega.distance(ratio, &e), &update_ok)
+            } else {
+                self.ncf
+                    .howard_pred(dist, |e| self.omega.distance(ratio, &e), &update_ok)
+            };
+            if let Some(ci) = ci_opt {
+                let ri = self.omega.zero_cancel(&ci);
+                if r_max < ri {
+                    r_max = ri;
+                    c_max = ci;
+                }
+            }
+            if r_max <= *ratio {
+                break;
+            }
+            cycle.clone_from(&c_max);
+            *ratio = r_max;
+            reverse = !reverse;
+        }
+        cycle
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use petgraph::graph::DiGraph;
+    use petgraph::visit::EdgeRef;
 
     use num::rational::Ratio;
 
     struct TestParametricAPI;
 
-    impl ParametricAPI<(), Ratio<i32>> for TestParametricAPI {
-        fn distance(&self, ratio: &Ratio<i32>, edge: &EdgeReference<Ratio<i32>>) -> Ratio<i32> {
+    impl<E: EdgeRef<Weight = Ratio<i32>>> ParametricAPI<E, Ratio<i32>> for TestParametricAPI {
+        fn distance(&self, ratio: &Ratio<i32>, edge: &E) -> Ratio<i32> {
             *edge.weight() - *ratio
         }
 
-        fn zero_cancel(&self, cycle: &[EdgeReference<Ratio<i32>>]) -> Ratio<i32> {
+        fn zero_cancel(&self, cycle: &[E]) -> Ratio<i32> {
             let mut sum_a = Ratio::new(0, 1);
             let mut sum_b = Ratio::new(0, 1);
             for edge in cycle {
@@ -247,4 +424,111 @@ mod tests {
         assert!(!cycle.is_empty());
         assert_eq!(ratio, Ratio::new(-3, 2)); // Should find the cycle with ratio -3/1
     }
+
+    struct TestMinParametricAPI;
+
+    impl<E: EdgeRef<Weight = Ratio<i32>>> ParametricAPI<E, Ratio<i32>> for TestMinParametricAPI {
+        fn distance(&self, ratio: &Ratio<i32>, edge: &E) -> Ratio<i32> {
+            *ratio - *edge.weight()
+        }
+
+        fn zero_cancel(&self, cycle: &[E]) -> Ratio<i32> {
+            let mut sum_a = Ratio::new(0, 1);
+            let mut sum_b = Ratio::new(0, 1);
+            for edge in cycle {
+                sum_a += *edge.weight();
+                sum_b += Ratio::new(1, 1);
+            }
+            sum_a / sum_b
+        }
+    }
+
+    #[test]
+    fn test_min_parametric_solver_simple_cycle() {
+        let digraph = DiGraph::<(), Ratio<i32>>::from_edges([
+            (0, 1, Ratio::new(1, 1)),
+            (1, 2, Ratio::new(1, 1)),
+            (2, 0, Ratio::new(3, 1)),
+        ]);
+
+        let mut solver = MinParametricSolver::new(&digraph, TestMinParametricAPI);
+        let mut dist = [Ratio::new(0, 1), Ratio::new(0, 1), Ratio::new(0, 1)];
+        let mut ratio = Ratio::new(0, 1);
+
+        let cycle = solver.run(&mut dist, &mut ratio);
+        assert!(!cycle.is_empty());
+        assert_eq!(ratio, Ratio::new(5, 3));
+    }
+
+    #[test]
+    fn test_min_parametric_solver_no_cycle() {
+        let digraph = DiGraph::<(), Ratio<i32>>::from_edges([
+            (0, 1, Ratio::new(1, 1)),
+            (1, 2, Ratio::new(1, 1)),
+            (0, 2, Ratio::new(3, 1)),
+        ]);
+
+        let mut solver = MinParametricSolver::new(&digraph, TestMinParametricAPI);
+        let mut dist = [Ratio::new(0, 1), Ratio::new(0, 1), Ratio::new(0, 1)];
+        let mut ratio = Ratio::new(0, 1);
+
+        let cycle = solver.run(&mut dist, &mut ratio);
+        assert!(cycle.is_empty());
+        assert_eq!(ratio, Ratio::new(0, 1)); // Should remain initial ratio
+    }
+
+    #[test]
+    fn test_min_parametric_solver_multiple_cycles() {
+        let digraph = DiGraph::<(), Ratio<i32>>::from_edges([
+            (0, 1, Ratio::new(1, 1)),
+            (1, 0, Ratio::new(2, 1)), // Cycle 1: ratio 3/2
+            (2, 3, Ratio::new(1, 1)),
+            (3, 2, Ratio::new(4, 1)), // Cycle 2: ratio 5/2
+            (0, 2, Ratio::new(1, 1)),
+        ]);
+
+        let mut solver = MinParametricSolver::new(&digraph, TestMinParametricAPI);
+        let mut dist = [
+            Ratio::new(0, 1),
+            Ratio::new(0, 1),
+            Ratio::new(0, 1),
+            Ratio::new(0, 1),
+        ];
+        let mut ratio = Ratio::new(0, 1);
+
+        let cycle = solver.run(&mut dist, &mut ratio);
+        assert!(!cycle.is_empty());
+        assert_eq!(ratio, Ratio::new(5, 2)); // Should find the cycle with ratio 5/2
+    }
+
+    #[test]
+    fn test_min_parametric_solver_pinned_node() {
+        // Cycle 0 <-> 1 has ratio 3/2 and cycle 2 <-> 3 has ratio 5/2. Node 2 is pinned at
+        // 0 while the other potentials stay positive, so `old != 0` rejects exactly the
+        // updates of node 2 and only the first cycle can be found.
+        let digraph = DiGraph::<(), Ratio<i32>>::from_edges([
+            (0, 1, Ratio::new(1, 1)),
+            (1, 0, Ratio::new(2, 1)),
+            (2, 3, Ratio::new(1, 1)),
+            (3, 2, Ratio::new(4, 1)),
+            (0, 2, Ratio::new(1, 1)),
+        ]);
+        let start = [100, 100, 0, 100].map(Ratio::from_integer);
+        let mut solver = MinParametricSolver::new(&digraph, TestMinParametricAPI);
+
+        let mut dist = start;
+        let mut ratio = Ratio::new(0, 1);
+        solver.run_q(&mut dist, &mut ratio, |_, _| true);
+        assert_eq!(ratio, Ratio::new(5, 2));
+
+        let mut dist = start;
+        let mut ratio = Ratio::new(0, 1);
+        let zero = Ratio::new(0, 1);
+        let cycle = solver.run_q(&mut dist, &mut ratio, |old, new| old != zero && new > zero);
+        assert_eq!(ratio, Ratio::new(3, 2));
+        assert!(cycle
+            .iter()
+            .all(|e| e.source().index() < 2 && e.target().index() < 2));
+        assert_eq!(dist[2], zero);
+    }
 }
@@ -0,0 +1,446 @@
+//! Dijkstra's algorithm over a path algebra.
+//!
+//! A [`PathAlgebra`] gives the value of the empty path, how an edge extends the value
+//! of a path, and which of two values is better. [`generalized_dijkstra`] then finds
+//! the best value from a source to every node. This is correct as long as extending a
+//! path never makes it better, which is checked on every scanned edge, and the
+//! comparison is a total order. The shipped instances:
+//!
+//! | Algebra              | Path value             | Better  | Edge weights  |
+//! |----------------------|------------------------|---------|---------------|
+//! | [`ShortestPath`]     | sum of weights         | smaller | non-negative  |
+//! | [`WidestPath`]       | minimum weight         | larger  | any           |
+//! | [`MostReliablePath`] | product of weights     | larger  | in `[0, 1]`   |
+//! | [`MinimaxPath`]      | maximum weight         | smaller | any           |
+//!
+//! [`PathAlgebra`]: crate::path_algebra::PathAlgebra
+//! [`generalized_dijkstra`]: crate::path_algebra::generalized_dijkstra
+//! [`ShortestPath`]: crate::path_algebra::ShortestPath
+//! [`WidestPath`]: crate::path_algebra::WidestPath
+//! [`MostReliablePath`]: crate::path_algebra::MostReliablePath
+//! [`MinimaxPath`]: crate::path_algebra::MinimaxPath
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::marker::PhantomData;
+use std::ops::Mul;
+
+use num::traits::{Bounded, One};
+use petgraph::visit::{
+    EdgeRef, IntoEdges, IntoNodeIdentifiers, NodeCount, NodeIndexable, VisitMap, Visitable,
+};
+
+use crate::dijkstra::DijkstraResult;
+use crate::error::NetOptimError;
+use crate::validate_node;
+use crate::weight::Weight;
+
+/// A way of valuing paths by their edge weights `W`.
+pub trait PathAlgebra<W> {
+    /// The value of a path.
+    type Value: Clone;
+
+    /// The value of the empty path.
+    fn identity(&self) -> Self::Value;
+
+    /// The value of a path with value `path` extended by an edge of weight `weight`,
+    /// or `None` if it cannot be represented (the edge is then ignored).
+    fn combine(&self, path: &Self::Value, weight: &W) -> Option<Self::Value>;
+
+    /// Compares two values: `Less` if `a` is better than `b`.
+    fn compare(&self, a: &Self::Value, b: &Self::Value) -> Ordering;
+}
+
+/// Shortest paths: the sum of the weights, smaller is better.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShortestPath;
+
+impl<W: Weight> PathAlgebra<W> for ShortestPath {
+    type Value = W;
+
+    fn identity(&self) -> W {
+        W::zero()
+    }
+
+    fn combine(&self, path: &W, weight: &W) -> Option<W> {
+        path.checked_add(weight)
+    }
+
+    fn compare(&self, a: &W, b: &W) -> Ordering {
+        a.partial_cmp(b).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Widest (maximum capacity) paths: the smallest weight on the path, larger is better.
+///
+/// The empty path has unlimited capacity, the largest value of `W`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WidestPath;
+
+impl<W: Clone + PartialOrd + Bounded> PathAlgebra<W> for WidestPath {
+    type Value = W;
+
+    fn identity(&self) -> W {
+        W::max_value()
+    }
+
+    fn combine(&self, path: &W, weight: &W) -> Option<W> {
+        Some(if weight < path { weight } else { path }.clone())
+    }
+
+    fn compare(&self, a: &W, b: &W) -> Ordering {
+        b.partial_cmp(a).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Most reliable paths: the product of the weights, which are probabilities in
+/// `[0, 1]`, larger is better.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MostReliablePath;
+
+impl<W: Clone + PartialOrd + One + Mul<Output = W>> PathAlgebra<W> for MostReliablePath {
+    type Value = W;
+
+    fn identity(&self) -> W {
+        W::one()
+    }
+
+    fn combine(&self, path: &W, weight: &W) -> Option<W> {
+        Some(path.clone() * weight.clone())
+    }
+
+    fn compare(&self, a: &W, b: &W) -> Ordering {
+        b.partial_cmp(a).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Minimax paths: the largest weight on the path, smaller is better.
+///
+/// The empty path has the smallest value of `W`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinimaxPath;
+
+impl<W: Clone + PartialOrd + Bounded> PathAlgebra<W> for MinimaxPath {
+    type Value = W;
+
+    fn identity(&self) -> W {
+        W::min_value()
+    }
+
+    fn combine(&self, path: &W, weight: &W) -> Option<W> {
+        Some(if path < weight { weight } else { path }.clone())
+    }
+
+    fn compare(&self, a: &W, b: &W) -> Ordering {
+        a.partial_cmp(b).unwrap_or(Ordering::Equal)
+    }
+}
+
+// Heap entry ordered by the algebra, the best value first.
+struct Entry<'a, A: PathAlgebra<W>, W, N> {
+    algebra: &'a A,
+    value: A::Value,
+    node: N,
+    weight: PhantomData<fn(&W)>,
+}
+
+impl<A: PathAlgebra<W>, W, N> PartialEq for Entry<'_, A, W, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<A: PathAlgebra<W>, W, N> Eq for Entry<'_, A, W, N> {}
+
+impl<A: PathAlgebra<W>, W, N> Ord for Entry<'_, A, W, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, so that the max-heap pops the best value.
+        self.algebra.compare(&other.value, &self.value)
+    }
+}
+
+impl<A: PathAlgebra<W>, W, N> PartialOrd for Entry<'_, A, W, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// \[Generic\] Compute the best path values from node `source` to all other nodes using
+/// Dijkstra's algorithm over the path algebra `algebra`.
+///
+/// With [`ShortestPath`] this computes the same distances as
+/// [`dijkstra`](crate::dijkstra::dijkstra). The returned `distances` are the best path
+/// values, `None` for unreachable nodes, and `predecessors` form a tree of best paths.
+///
+/// # Arguments
+/// * `g` - The graph to search
+/// * `source` - The source node index
+/// * `algebra` - How paths are valued and compared
+///
+/// # Returns
+/// * `Ok(DijkstraResult)` - Contains the best values and predecessors for each node
+/// * `Err(NetOptimError::NonMonotoneEdge)` - With the first scanned edge that improves
+///   a path, such as a negative weight for shortest paths
+/// * `Err(NetOptimError::InvalidNode)` - If `source` is not a node of `g`
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::prelude::*;
+/// use netoptim_rs::path_algebra::{generalized_dijkstra, MinimaxPath, ShortestPath, WidestPath};
+///
+/// // Link capacities.
+/// let g = Graph::<(), u32>::from_edges(&[(0, 1, 5), (1, 3, 3), (0, 2, 2), (2, 3, 10), (0, 3, 1)]);
+/// let a = NodeIndex::new(0);
+///
+/// // The best bandwidth to node 3 is 3, through node 1.
+/// let widest = generalized_dijkstra(&g, a, WidestPath).unwrap();
+/// assert_eq!(widest.distances, vec![Some(u32::MAX), Some(5), Some(2), Some(3)]);
+/// assert_eq!(widest.predecessors[3], Some(NodeIndex::new(1)));
+///
+/// let minimax = generalized_dijkstra(&g, a, MinimaxPath).unwrap();
+/// assert_eq!(minimax.distances, vec![Some(0), Some(5), Some(2), Some(1)]);
+///
+/// let shortest = generalized_dijkstra(&g, a, ShortestPath).unwrap();
+/// assert_eq!(shortest.distances, vec![Some(0), Some(5), Some(2), Some(1)]);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn generalized_dijkstra<G, A>(
+    g: G,
+    source: G::NodeId,
+    algebra: A,
+) -> Result<DijkstraResult<G::NodeId, A::Value>, NetOptimError<G::NodeId>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable + Visitable,
+    A: PathAlgebra<G::EdgeWeight>,
+{
+    validate_node(g, source)?;
+    let ix = |i| g.to_index(i);
+
+    let mut distances: Vec<Option<A::Value>> = vec![None; g.node_bound()];
+    let mut predecessors = vec![None; g.node_bound()];
+    let mut visited = g.visit_map();
+
+    distances[ix(source)] = Some(algebra.identity());
+
+    let mut heap = BinaryHeap::new();
+    heap.push(Entry {
+        algebra: &algebra,
+        value: algebra.identity(),
+        node: source,
+        weight: PhantomData,
+    });
+
+    while let Some(Entry { value, node, .. }) = heap.pop() {
+        if !visited.visit(node) {
+            continue;
+        }
+
+        for edge in g.edges(node) {
+            let target = edge.target();
+            let Some(new_value) = algebra.combine(&value, edge.weight()) else {
+                continue;
+            };
+            if algebra.compare(&new_value, &value) == Ordering::Less {
+                return Err(NetOptimError::NonMonotoneEdge {
+                    source: node,
+                    target,
+                });
+            }
+
+            if visited.is_visited(&target) {
+                continue;
+            }
+            if distances[ix(target)]
+                .as_ref()
+                .is_none_or(|current| algebra.compare(&new_value, current) == Ordering::Less)
+            {
+                distances[ix(target)] = Some(new_value.clone());
+                predecessors[ix(target)] = Some(node);
+                heap.push(Entry {
+                    algebra: &algebra,
+                    value: new_value,
+                    node: target,
+                    weight: PhantomData,
+                });
+            }
+        }
+    }
+
+    Ok(DijkstraResult {
+        distances,
+        predecessors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstra::dijkstra;
+    use crate::test_utils::{self, Lcg};
+    use petgraph::prelude::*;
+    use petgraph::Graph;
+
+    fn random_graph(seed: u64, max_weight: u64) -> Graph<(), u64> {
+        let mut rng = Lcg::new(seed);
+        let n = 2 + rng.below(12) as usize;
+        let m = rng.below(40) as usize;
+        test_utils::random_graph(&mut rng, n, m, false, |rng| 1 + rng.below(max_weight))
+    }
+
+    // Whether `target` is reachable from `source` over the edges that pass `keep`.
+    fn reachable(
+        g: &Graph<(), u64>,
+        source: NodeIndex,
+        target: NodeIndex,
+        keep: impl Fn(u64) -> bool,
+    ) -> bool {
+        let mut seen = vec![false; g.node_count()];
+        let mut stack = vec![source];
+        seen[source.index()] = true;
+        while let Some(u) = stack.pop() {
+            for edge in g.edges(u) {
+                if keep(*edge.weight()) && !seen[edge.target().index()] {
+                    seen[edge.target().index()] = true;
+                    stack.push(edge.target());
+                }
+            }
+        }
+        seen[target.index()]
+    }
+
+    #[test]
+    fn test_shortest_path_matches_dijkstra() {
+        for seed in 0..100 {
+            let g = random_graph(seed, 20);
+            for s in g.node_indices() {
+                let paths = generalized_dijkstra(&g, s, ShortestPath).unwrap();
+                assert_eq!(paths.distances, dijkstra(&g, s).unwrap().distances);
+            }
+        }
+    }
+
+    #[test]
+    fn test_widest_path_is_best_bottleneck() {
+        // The widest path to t has bottleneck c iff t is reachable over the edges of
+        // capacity at least c, but not over those above c.
+        for seed in 0..100 {
+            let g = random_graph(seed, 10);
+            let s = NodeIndex::new(0);
+            let paths = generalized_dijkstra(&g, s, WidestPath).unwrap();
+            for t in g.node_indices().skip(1) {
+                match paths.distances[t.index()] {
+                    Some(c) => {
+                        assert!(reachable(&g, s, t, |w| w >= c));
+                        assert!(!reachable(&g, s, t, |w| w > c));
+                    }
+                    None => assert!(!reachable(&g, s, t, |_| true)),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_minimax_path_is_best_bottleneck() {
+        for seed in 0..100 {
+            let g = random_graph(seed, 10);
+            let s = NodeIndex::new(0);
+            let paths = generalized_dijkstra(&g, s, MinimaxPath).unwrap();
+            for t in g.node_indices().skip(1) {
+                match paths.distances[t.index()] {
+                    Some(c) => {
+                        assert!(reachable(&g, s, t, |w| w <= c));
+                        assert!(!reachable(&g, s, t, |w| w < c));
+                    }
+                    None => assert!(!reachable(&g, s, t, |_| true)),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_most_reliable_path() {
+        // Direct link 0.5; through 1 0.9 * 0.8 = 0.72; through 2 0.99 * 0.7 = 0.693.
+        let g = Graph::<(), f64>::from_edges([
+            (0, 3, 0.5),
+            (0, 1, 0.9),
+            (1, 3, 0.8),
+            (0, 2, 0.99),
+            (2, 3, 0.7),
+        ]);
+        let paths = generalized_dijkstra(&g, NodeIndex::new(0), MostReliablePath).unwrap();
+        assert_eq!(paths.distances[3], Some(0.9 * 0.8));
+        assert_eq!(paths.predecessors[3], Some(NodeIndex::new(1)));
+        assert_eq!(paths.distances[0], Some(1.0));
+    }
+
+    #[test]
+    fn test_most_reliable_path_agrees_with_log_costs() {
+        // Maximizing a product of probabilities minimizes the sum of -ln(p).
+        for seed in 0..50 {
+            let g = random_graph(seed, 100).map(|_, _| (), |_, &w| w as f64 / 100.0);
+            let s = NodeIndex::new(0);
+            let reliable = generalized_dijkstra(&g, s, MostReliablePath).unwrap();
+            let costs = g.map(|_, _| (), |_, &p| -f64::ln(p));
+            let shortest = dijkstra(&costs, s).unwrap();
+            for t in g.node_indices() {
+                let expected = shortest.distances[t.index()].map(|d| (-d).exp());
+                match (reliable.distances[t.index()], expected) {
+                    (Some(p), Some(q)) => assert!((p - q).abs() < 1e-9),
+                    (p, q) => assert_eq!(p, q),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_monotone_edges_are_rejected() {
+        let n = NodeIndex::new;
+        let g = Graph::<(), i32>::from_edges([(0, 1, 2), (1, 2, -1)]);
+        assert_eq!(
+            generalized_dijkstra(&g, n(0), ShortestPath).unwrap_err(),
+            NetOptimError::NonMonotoneEdge {
+                source: n(1),
+                target: n(2)
+            }
+        );
+        let g = Graph::<(), f64>::from_edges([(0, 1, 0.5), (1, 2, 1.5)]);
+        assert_eq!(
+            generalized_dijkstra(&g, n(0), MostReliablePath).unwrap_err(),
+            NetOptimError::NonMonotoneEdge {
+                source: n(1),
+                target: n(2)
+            }
+        );
+    }
+
+    #[test]
+    fn test_generalized_dijkstra_invalid_source() {
+        let g = Graph::<(), u32>::from_edges([(0, 1, 1)]);
+        assert_eq!(
+            generalized_dijkstra(&g, NodeIndex::new(4), WidestPath).unwrap_err(),
+            NetOptimError::InvalidNode(NodeIndex::new(4))
+        );
+    }
+
+    #[test]
+    fn test_custom_path_algebra() {
+        // Fewest hops, ignoring the weights.
+        struct Hops;
+        impl<W> PathAlgebra<W> for Hops {
+            type Value = usize;
+            fn identity(&self) -> usize {
+                0
+            }
+            fn combine(&self, path: &usize, _: &W) -> Option<usize> {
+                Some(path + 1)
+            }
+            fn compare(&self, a: &usize, b: &usize) -> Ordering {
+                a.cmp(b)
+            }
+        }
+        let g = Graph::<(), f64>::from_edges([(0, 1, 1.0), (1, 2, 1.0), (0, 2, 10.0)]);
+        let paths = generalized_dijkstra(&g, NodeIndex::new(0), Hops).unwrap();
+        assert_eq!(paths.distances, vec![Some(0), Some(1), Some(1)]);
+    }
+}
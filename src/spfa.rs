@@ -0,0 +1,280 @@
+//! Queue-based Bellman-Ford with subtree disassembly.
+//!
+//! A FIFO label-correcting method: only nodes whose distance changed are rescanned,
+//! instead of sweeping every edge on every pass. The shortest path tree is kept as a
+//! preorder thread so that, when a node's distance improves, its whole subtree can
+//! be disassembled (Tarjan's heuristic). Disassembled nodes are not scanned until
+//! they are reached again, and a relaxation whose tail lies in the head's subtree
+//! closes a negative cycle, which is reported immediately.
+
+use std::collections::VecDeque;
+
+use petgraph::visit::{EdgeRef, IntoEdges, IntoNodeIdentifiers, NodeCount, NodeIndexable};
+
+use crate::error::NetOptimError;
+use crate::weight::Weight;
+use crate::{relaxed_distance, validate_node, Overflowed, Paths};
+
+/// \[Generic\] Compute shortest paths from node `source` to all other using a
+/// queue-based Bellman-Ford (SPFA) with Tarjan's subtree disassembly.
+///
+/// Returns the same [`Paths`] as [`bellman_ford`](crate::bellman_ford), or the same
+/// errors: [`NetOptimError::NegativeCycle`] with the nodes of a negative cycle reachable
+/// from `source`, found as soon as it closes. The worst case is still O(|V|·|E|), but on
+/// sparse graphs only a small fraction of the edges is rescanned.
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::prelude::*;
+/// use netoptim_rs::{spfa, NetOptimError};
+///
+/// let g = Graph::<(), i32>::from_edges(&[(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, -1)]);
+/// let paths = spfa(&g, NodeIndex::new(0)).unwrap();
+/// assert_eq!(paths.distances, vec![Some(0), Some(3), Some(1), Some(2)]);
+/// assert_eq!(paths.predecessors[1], Some(NodeIndex::new(2)));
+///
+/// let g = Graph::<(), i32>::from_edges(&[(0, 1, 1), (1, 2, -2), (2, 1, 1)]);
+/// let err = spfa(&g, NodeIndex::new(0)).unwrap_err();
+/// assert_eq!(err, NetOptimError::NegativeCycle(vec![1.into(), 2.into()]));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn spfa<G>(
+    g: G,
+    source: G::NodeId,
+) -> Result<Paths<G::NodeId, G::EdgeWeight>, NetOptimError<G::NodeId>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable,
+    G::EdgeWeight: Weight,
+{
+    validate_node(g, source)?;
+    let n = g.node_bound();
+    let ix = |i| g.to_index(i);
+
+    let mut distances = vec![None; n];
+    let mut predecessors = vec![None; n];
+
+    // Shortest path tree as a circular preorder thread rooted at `source`.
+    let mut next = vec![0; n];
+    let mut prev = vec![0; n];
+    let mut depth = vec![0; n];
+    let mut in_tree = vec![false; n];
+    let mut in_queue = vec![false; n];
+
+    let s = ix(source);
+    distances[s] = Some(G::EdgeWeight::zero());
+    next[s] = s;
+    prev[s] = s;
+    in_tree[s] = true;
+    in_queue[s] = true;
+
+    let mut overflowed = Overflowed::default();
+    let mut queue = VecDeque::with_capacity(g.node_count());
+    queue.push_back(source);
+
+    while let Some(u) = queue.pop_front() {
+        let ux = ix(u);
+        in_queue[ux] = false;
+        // Disassembled while waiting in the queue; it will be requeued once reached again.
+        if !in_tree[ux] {
+            continue;
+        }
+
+        for edge in g.edges(u) {
+            let v = edge.target();
+            let vx = ix(v);
+            let relaxed = relaxed_distance(
+                &distances[ux],
+                &distances[vx],
+                edge.weight(),
+                (ux, vx),
+                &mut overflowed,
+            );
+            let Some(d) = relaxed? else {
+                continue;
+            };
+
+            if in_tree[vx] {
+                if vx == ux {
+                    return Err(NetOptimError::NegativeCycle(vec![u]));
+                }
+                // Disassemble the subtree below `v`; finding `u` there closes a cycle,
+                // which is the tree path from `v` down to `u`.
+                let mut w = next[vx];
+                while depth[w] > depth[vx] {
+                    if w == ux {
+                        let mut cycle = vec![u];
+                        let mut node = u;
+                        while node != v {
+                            node = predecessors[ix(node)].expect("tree nodes have a parent");
+                            cycle.push(node);
+                        }
+                        cycle.reverse();
+                        return Err(NetOptimError::NegativeCycle(cycle));
+                    }
+                    in_tree[w] = false;
+                    w = next[w];
+                }
+                // Unlink `v` and its former subtree from the thread.
+                next[prev[vx]] = w;
+                prev[w] = prev[vx];
+            }
+
+            // Attach `v` as the first child of `u`.
+            distances[vx] = Some(d);
+            predecessors[vx] = Some(u);
+            depth[vx] = depth[ux] + 1;
+            in_tree[vx] = true;
+            let after = next[ux];
+            next[ux] = vx;
+            prev[vx] = ux;
+            next[vx] = after;
+            prev[after] = vx;
+
+            if !in_queue[vx] {
+                in_queue[vx] = true;
+                queue.push_back(v);
+            }
+        }
+    }
+
+    overflowed.check(&distances)?;
+    Ok(Paths {
+        distances,
+        predecessors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bellman_ford;
+    use crate::test_utils::{random_graph, Lcg};
+    use petgraph::prelude::*;
+    use petgraph::Graph;
+
+    #[test]
+    fn test_spfa_matches_bellman_ford() {
+        let graph = Graph::<(), i64, Directed>::from_edges([
+            (0, 1, 6),
+            (0, 2, 7),
+            (1, 2, 8),
+            (1, 3, 5),
+            (1, 4, -4),
+            (2, 3, -3),
+            (2, 4, 9),
+            (3, 1, -2),
+            (4, 0, 2),
+            (4, 3, 7),
+        ]);
+        let paths = spfa(&graph, NodeIndex::new(0)).unwrap();
+        let expected = bellman_ford(&graph, NodeIndex::new(0)).unwrap();
+        assert_eq!(paths.distances, expected.distances);
+        assert_eq!(
+            paths.distances,
+            vec![Some(0), Some(2), Some(7), Some(4), Some(-2)]
+        );
+    }
+
+    #[test]
+    fn test_spfa_predecessors_form_shortest_path_tree() {
+        let graph = Graph::<(), i32, Directed>::from_edges([
+            (0, 1, 10),
+            (0, 2, 1),
+            (2, 3, 1),
+            (3, 1, 1),
+            (1, 4, 1),
+            (4, 5, 1),
+        ]);
+        let paths = spfa(&graph, NodeIndex::new(0)).unwrap();
+        for edge in graph.edge_references() {
+            let (u, v) = (edge.source().index(), edge.target().index());
+            if paths.predecessors[v] == Some(edge.source()) {
+                assert_eq!(
+                    paths.distances[v],
+                    Some(paths.distances[u].unwrap() + edge.weight())
+                );
+            }
+        }
+        assert_eq!(paths.distances[5], Some(5));
+        assert_eq!(paths.predecessors[1], Some(NodeIndex::new(3)));
+    }
+
+    #[test]
+    fn test_spfa_negative_cycle() {
+        let graph = Graph::<(), f64, Directed>::from_edges([
+            (0, 1, 1.0),
+            (1, 2, 1.0),
+            (2, 3, 1.0),
+            (3, 1, -4.0),
+        ]);
+        let n = NodeIndex::new;
+        assert_eq!(
+            spfa(&graph, n(0)).unwrap_err(),
+            NetOptimError::NegativeCycle(vec![n(1), n(2), n(3)])
+        );
+    }
+
+    #[test]
+    fn test_spfa_negative_self_loop() {
+        let graph = Graph::<(), i32, Directed>::from_edges([(0, 1, 1), (1, 1, -1)]);
+        assert_eq!(
+            spfa(&graph, NodeIndex::new(0)).unwrap_err(),
+            NetOptimError::NegativeCycle(vec![NodeIndex::new(1)])
+        );
+    }
+
+    #[test]
+    fn test_spfa_negative_cycle_through_source() {
+        let graph = Graph::<(), i32, Directed>::from_edges([(0, 1, 1), (1, 0, -2)]);
+        assert!(matches!(
+            spfa(&graph, NodeIndex::new(0)),
+            Err(NetOptimError::NegativeCycle(cycle)) if cycle.len() == 2
+        ));
+    }
+
+    #[test]
+    fn test_spfa_invalid_source() {
+        let graph = Graph::<(), i32, Directed>::from_edges([(0, 1, 1)]);
+        assert_eq!(
+            spfa(&graph, NodeIndex::new(2)).unwrap_err(),
+            NetOptimError::InvalidNode(NodeIndex::new(2))
+        );
+    }
+
+    #[test]
+    fn test_spfa_unreachable_negative_cycle() {
+        let graph = Graph::<(), i32, Directed>::from_edges([(0, 1, 1), (2, 3, -1), (3, 2, -1)]);
+        let paths = spfa(&graph, NodeIndex::new(0)).unwrap();
+        assert_eq!(paths.distances, vec![Some(0), Some(1), None, None]);
+    }
+
+    #[test]
+    fn test_spfa_agrees_with_bellman_ford_on_random_graphs() {
+        let mut rng = Lcg::new(0x2545_f491);
+        for _ in 0..200 {
+            let n = 2 + rng.below(10) as usize;
+            let m = rng.below(30) as usize;
+            let graph = random_graph(&mut rng, n, m, false, |rng| rng.below(20) as i64 - 4);
+            let source = NodeIndex::new(0);
+            match (spfa(&graph, source), bellman_ford(&graph, source)) {
+                (Ok(paths), Ok(expected)) => assert_eq!(paths.distances, expected.distances),
+                (Err(_), Err(_)) => {}
+                _ => panic!("spfa and bellman_ford disagree on negative cycles"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_spfa_stable_graph_with_holes() {
+        let mut graph = StableDiGraph::<(), i32>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        let c = graph.add_node(());
+        graph.add_edge(a, c, 2);
+        graph.remove_node(b);
+        let paths = spfa(&graph, a).unwrap();
+        assert_eq!(paths.distances[c.index()], Some(2));
+        assert_eq!(paths.predecessors[c.index()], Some(a));
+    }
+}
@@ -0,0 +1,429 @@
+//! Every generic algorithm on a `StableGraph` with removed nodes.
+//!
+//! Removing nodes leaves holes in the index space, so `node_bound()` exceeds
+//! `node_count()`. Each algorithm is run on such a graph and on a copy with its nodes
+//! renumbered densely, and the results must agree node for node.
+
+use num::rational::Ratio;
+use petgraph::graph::DiGraph;
+use petgraph::prelude::*;
+use petgraph::stable_graph::StableDiGraph;
+use petgraph::visit::{IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+use crate::dijkstra::{
+    astar, bidirectional_dijkstra, dijkstra, dijkstra_path, dijkstra_with_options,
+    dijkstra_with_potentials, dijkstra_with_queue, DijkstraOptions,
+};
+use crate::error::NetOptimError;
+use crate::floyd_warshall::floyd_warshall;
+use crate::johnson::johnson;
+use crate::karp::min_mean_cycle;
+use crate::min_cycle_ratio::MinCycleRatioSolver;
+use crate::monotone_queue::{BucketQueue, RadixHeap};
+use crate::neg_cycle::NegCycleFinder;
+use crate::neg_cycle_q::NegCycleFinderQ;
+use crate::parametric::{MaxParametricSolver, MinParametricSolver, ParametricAPI};
+use crate::path_algebra::{generalized_dijkstra, MinimaxPath, ShortestPath, WidestPath};
+use crate::test_utils::{self, Lcg};
+use crate::yen::k_shortest_paths;
+use crate::{
+    bellman_ford, bellman_ford_multi_source, bellman_ford_virtual_source, find_negative_cycle,
+    goldberg_radzik, spfa, BellmanFordError,
+};
+
+const NODES: usize = 12;
+// Includes the first node; the last one stays, so `node_bound()` is `NODES`.
+const REMOVED: [usize; 4] = [0, 3, 4, 8];
+
+// A graph with removed nodes and its densely numbered copy; `dense[v.index()]` is the
+// copy of `v`.
+struct Holey {
+    stable: StableDiGraph<(), i64>,
+    compact: DiGraph<(), i64>,
+    dense: Vec<Option<NodeIndex>>,
+}
+
+impl Holey {
+    fn new(edges: &[(usize, usize, i64)]) -> Self {
+        let mut stable = StableDiGraph::new();
+        for _ in 0..NODES {
+            stable.add_node(());
+        }
+        for &(u, v, w) in edges {
+            stable.add_edge(NodeIndex::new(u), NodeIndex::new(v), w);
+        }
+        for &v in &REMOVED {
+            stable.remove_node(NodeIndex::new(v));
+        }
+        assert!(stable.node_count() < stable.node_bound());
+
+        let mut compact = DiGraph::new();
+        let mut dense = vec![None; stable.node_bound()];
+        for v in stable.node_indices() {
+            dense[v.index()] = Some(compact.add_node(()));
+        }
+        for edge in stable.edge_references() {
+            let (u, v) = (edge.source().index(), edge.target().index());
+            compact.add_edge(dense[u].unwrap(), dense[v].unwrap(), *edge.weight());
+        }
+        Holey {
+            stable,
+            compact,
+            dense,
+        }
+    }
+
+    fn map(&self, v: NodeIndex) -> NodeIndex {
+        self.dense[v.index()].expect("live node")
+    }
+
+    fn live(&self) -> Vec<NodeIndex> {
+        self.stable.node_identifiers().collect()
+    }
+
+    // Per-node results agree on live nodes and are empty on the holes.
+    fn assert_same_distances(&self, stable: &[Option<i64>], compact: &[Option<i64>]) {
+        assert_eq!(stable.len(), self.stable.node_bound());
+        assert_eq!(compact.len(), self.compact.node_count());
+        for v in self.live() {
+            assert_eq!(
+                stable[v.index()],
+                compact[self.map(v).index()],
+                "node {:?}",
+                v
+            );
+        }
+        for &v in &REMOVED {
+            assert_eq!(stable[v], None);
+        }
+    }
+
+    fn assert_same_cycle(&self, stable: Vec<NodeIndex>, compact: Vec<NodeIndex>) {
+        let mut mapped: Vec<NodeIndex> = stable.into_iter().map(|v| self.map(v)).collect();
+        let mut compact = compact;
+        mapped.sort();
+        compact.sort();
+        assert_eq!(mapped, compact);
+    }
+}
+
+// Edges between random node pairs. Forward edges only if `dag`, so that negative
+// weights cannot form a cycle.
+fn random_edges(m: usize, weights: std::ops::Range<i64>, dag: bool) -> Vec<(usize, usize, i64)> {
+    let span = (weights.end - weights.start) as u64;
+    test_utils::random_edges(&mut Lcg::new(0x2545_f491), NODES, m, dag, |rng| {
+        weights.start + rng.below(span) as i64
+    })
+}
+
+// The bottleneck algebras, to run both in one loop.
+#[derive(Clone, Copy)]
+enum Bottleneck {
+    Widest,
+    Minimax,
+}
+
+impl From<WidestPath> for Bottleneck {
+    fn from(_: WidestPath) -> Self {
+        Bottleneck::Widest
+    }
+}
+
+impl From<MinimaxPath> for Bottleneck {
+    fn from(_: MinimaxPath) -> Self {
+        Bottleneck::Minimax
+    }
+}
+
+fn run_algebra<G>(g: G, s: NodeIndex, algebra: Bottleneck) -> Vec<Option<i64>>
+where
+    G: petgraph::visit::IntoEdges<NodeId = NodeIndex, EdgeWeight = i64>
+        + IntoNodeIdentifiers
+        + petgraph::visit::NodeCount
+        + NodeIndexable
+        + petgraph::visit::Visitable,
+{
+    match algebra {
+        Bottleneck::Widest => generalized_dijkstra(g, s, WidestPath),
+        Bottleneck::Minimax => generalized_dijkstra(g, s, MinimaxPath),
+    }
+    .unwrap()
+    .distances
+}
+
+fn path_cost(g: &StableDiGraph<(), i64>, path: &[NodeIndex]) -> i64 {
+    path.windows(2)
+        .map(|w| {
+            g.edges_connecting(w[0], w[1])
+                .map(|e| *e.weight())
+                .min()
+                .unwrap()
+        })
+        .sum()
+}
+
+#[test]
+fn test_stable_graph_dijkstra() {
+    let h = Holey::new(&random_edges(40, 0..20, false));
+    for s in h.live() {
+        let stable = dijkstra(&h.stable, s).unwrap();
+        let compact = dijkstra(&h.compact, h.map(s)).unwrap();
+        h.assert_same_distances(&stable.distances, &compact.distances);
+        for v in h.live() {
+            let pred = stable.predecessors[v.index()].map(|p| h.map(p));
+            assert_eq!(pred, compact.predecessors[h.map(v).index()]);
+        }
+
+        let stable = dijkstra_with_queue(&h.stable, s, BucketQueue::new()).unwrap();
+        h.assert_same_distances(&stable.distances, &compact.distances);
+        let stable = dijkstra_with_queue(&h.stable, s, RadixHeap::new()).unwrap();
+        h.assert_same_distances(&stable.distances, &compact.distances);
+
+        let stable = generalized_dijkstra(&h.stable, s, ShortestPath).unwrap();
+        h.assert_same_distances(&stable.distances, &compact.distances);
+        for algebra in [WidestPath.into(), MinimaxPath.into()] {
+            let stable = run_algebra(&h.stable, s, algebra);
+            let compact = run_algebra(&h.compact, h.map(s), algebra);
+            h.assert_same_distances(&stable, &compact);
+        }
+
+        let options = DijkstraOptions::new().with_radius(15);
+        let stable = dijkstra_with_options(&h.stable, s, &options).unwrap();
+        let compact = dijkstra_with_options(&h.compact, h.map(s), &options).unwrap();
+        h.assert_same_distances(&stable.distances, &compact.distances);
+    }
+}
+
+#[test]
+fn test_stable_graph_point_to_point() {
+    let h = Holey::new(&random_edges(30, 0..20, false));
+    for s in h.live() {
+        let expected = dijkstra(&h.compact, h.map(s)).unwrap().distances;
+        for t in h.live() {
+            let distance = expected[h.map(t).index()];
+            let cost = |path: Result<Vec<NodeIndex>, _>| path.map(|p| path_cost(&h.stable, &p));
+
+            assert_eq!(cost(dijkstra_path(&h.stable, s, t)).ok(), distance);
+            assert_eq!(cost(bidirectional_dijkstra(&h.stable, s, t)).ok(), distance);
+            assert_eq!(astar(&h.stable, s, t, |_| 0).ok().map(|(c, _)| c), distance);
+            if distance.is_none() {
+                assert_eq!(dijkstra_path(&h.stable, s, t), Err(NetOptimError::NoPath));
+            }
+
+            let stable = k_shortest_paths(&h.stable, s, t, 3).unwrap();
+            let compact = k_shortest_paths(&h.compact, h.map(s), h.map(t), 3).unwrap();
+            let costs = |paths: &[(i64, Vec<NodeIndex>)]| -> Vec<i64> {
+                paths.iter().map(|(c, _)| *c).collect()
+            };
+            assert_eq!(costs(&stable), costs(&compact));
+        }
+    }
+}
+
+#[test]
+fn test_stable_graph_removed_nodes_are_invalid() {
+    let h = Holey::new(&random_edges(30, 0..20, false));
+    let s = h.live()[0];
+    let potentials = vec![0; h.stable.node_bound()];
+    // Removed nodes lie below `node_bound()`, like the one index past it.
+    for v in REMOVED.into_iter().chain([NODES]).map(NodeIndex::new) {
+        let invalid = NetOptimError::InvalidNode(v);
+        let is_invalid = |err: NetOptimError<NodeIndex>| err == invalid;
+
+        assert!(is_invalid(dijkstra(&h.stable, v).unwrap_err()));
+        let options = DijkstraOptions::new().with_targets([v]);
+        assert!(is_invalid(
+            dijkstra_with_options(&h.stable, s, &options).unwrap_err()
+        ));
+        let result = dijkstra_with_potentials(&h.stable, v, &potentials);
+        assert!(is_invalid(result.unwrap_err()));
+        let result = dijkstra_with_queue(&h.stable, v, RadixHeap::new());
+        assert!(is_invalid(result.unwrap_err()));
+        let result = generalized_dijkstra(&h.stable, v, ShortestPath);
+        assert!(is_invalid(result.unwrap_err()));
+        for (a, b) in [(s, v), (v, s)] {
+            assert!(is_invalid(dijkstra_path(&h.stable, a, b).unwrap_err()));
+            assert!(is_invalid(
+                bidirectional_dijkstra(&h.stable, a, b).unwrap_err()
+            ));
+            assert!(is_invalid(astar(&h.stable, a, b, |_| 0).unwrap_err()));
+            assert!(is_invalid(
+                k_shortest_paths(&h.stable, a, b, 2).unwrap_err()
+            ));
+        }
+
+        assert!(is_invalid(bellman_ford(&h.stable, v).unwrap_err()));
+        assert!(is_invalid(spfa(&h.stable, v).unwrap_err()));
+        assert!(is_invalid(goldberg_radzik(&h.stable, v).unwrap_err()));
+        assert!(is_invalid(find_negative_cycle(&h.stable, v).unwrap_err()));
+        let result = bellman_ford_multi_source(&h.stable, [(s, 0), (v, 0)]);
+        assert!(is_invalid(result.unwrap_err().into()));
+    }
+}
+
+#[test]
+fn test_stable_graph_bellman_ford_family() {
+    let h = Holey::new(&random_edges(40, -5..15, true));
+    for s in h.live() {
+        let expected = bellman_ford(&h.compact, h.map(s)).unwrap().distances;
+        let stable = bellman_ford(&h.stable, s).unwrap();
+        h.assert_same_distances(&stable.distances, &expected);
+        let stable = spfa(&h.stable, s).unwrap();
+        h.assert_same_distances(&stable.distances, &expected);
+        let stable = goldberg_radzik(&h.stable, s).unwrap();
+        h.assert_same_distances(&stable.distances, &expected);
+        assert_eq!(find_negative_cycle(&h.stable, s), Ok(None));
+    }
+
+    let live = h.live();
+    let sources = [(live[0], 3), (live[2], 0)];
+    let stable = bellman_ford_multi_source(&h.stable, sources).unwrap();
+    let mapped = sources.map(|(v, d)| (h.map(v), d));
+    let compact = bellman_ford_multi_source(&h.compact, mapped).unwrap();
+    h.assert_same_distances(&stable.distances, &compact.distances);
+
+    let stable = bellman_ford_virtual_source(&h.stable).unwrap();
+    let compact = bellman_ford_virtual_source(&h.compact).unwrap();
+    h.assert_same_distances(&stable.distances, &compact.distances);
+
+    let potentials: Vec<i64> = stable.distances.iter().map(|p| p.unwrap_or(0)).collect();
+    for s in h.live() {
+        let expected = bellman_ford(&h.compact, h.map(s)).unwrap().distances;
+        let stable = dijkstra_with_potentials(&h.stable, s, &potentials).unwrap();
+        h.assert_same_distances(&stable.distances, &expected);
+    }
+}
+
+#[test]
+fn test_stable_graph_all_pairs() {
+    let h = Holey::new(&random_edges(40, -5..15, true));
+    let stable = johnson(&h.stable).unwrap();
+    let compact = johnson(&h.compact).unwrap();
+    let floyd = floyd_warshall(&h.stable);
+    assert_eq!(stable.distances.len(), h.stable.node_bound());
+    for s in h.live() {
+        let expected = &compact.distances[h.map(s).index()];
+        h.assert_same_distances(&stable.distances[s.index()], expected);
+        h.assert_same_distances(&floyd.paths.distances[s.index()], expected);
+        for t in h.live() {
+            let path = stable.path(&h.stable, s, t);
+            let distance = expected[h.map(t).index()];
+            assert_eq!(path.map(|p| path_cost(&h.stable, &p)), distance);
+        }
+    }
+}
+
+#[test]
+fn test_stable_graph_negative_cycles() {
+    // The only negative cycle left is 5 -> 6 -> 7 -> 5; the one through 3 is removed.
+    let mut edges = random_edges(30, 10..20, true);
+    edges.extend([(5, 6, 1), (6, 7, 1), (7, 5, -4), (2, 3, 1), (3, 2, -5)]);
+    let h = Holey::new(&edges);
+    let s = NodeIndex::new(5);
+
+    let Err(NetOptimError::NegativeCycle(compact)) = bellman_ford(&h.compact, h.map(s)) else {
+        panic!("expected a negative cycle");
+    };
+    for result in [
+        bellman_ford(&h.stable, s),
+        spfa(&h.stable, s),
+        goldberg_radzik(&h.stable, s),
+    ] {
+        let Err(NetOptimError::NegativeCycle(stable)) = result else {
+            panic!("expected a negative cycle");
+        };
+        h.assert_same_cycle(stable, compact.clone());
+    }
+    let Err(NetOptimError::NegativeCycle(stable)) = johnson(&h.stable) else {
+        panic!("expected a negative cycle");
+    };
+    h.assert_same_cycle(stable, compact.clone());
+    let stable = find_negative_cycle(&h.stable, s).unwrap().unwrap();
+    h.assert_same_cycle(stable, compact.clone());
+    let Err(BellmanFordError::NegativeCycle(stable)) = bellman_ford_virtual_source(&h.stable)
+    else {
+        panic!("expected a negative cycle");
+    };
+    h.assert_same_cycle(stable.nodes, compact.clone());
+
+    let weight = |cycle: &[_]| -> i64 {
+        cycle
+            .iter()
+            .map(|e: &petgraph::stable_graph::EdgeReference<i64>| *e.weight())
+            .sum()
+    };
+    let mut ncf = NegCycleFinder::new(&h.stable);
+    let mut dist = vec![0; h.stable.node_bound()];
+    assert_eq!(weight(&ncf.howard(&mut dist, |e| *e.weight()).unwrap()), -2);
+    let mut ncf = NegCycleFinderQ::new(&h.stable);
+    let mut dist = vec![0; h.stable.node_bound()];
+    let cycle = ncf.howard_pred(&mut dist, |e| *e.weight(), |_, _| true);
+    assert_eq!(weight(&cycle.unwrap()), -2);
+    let mut dist = vec![0; h.stable.node_bound()];
+    let cycle = ncf.howard_succ(&mut dist, |e| *e.weight(), |_, _| true);
+    assert_eq!(weight(&cycle.unwrap()), -2);
+
+    let stable = min_mean_cycle(&h.stable, |e| *e.weight() as f64).unwrap();
+    let compact = min_mean_cycle(&h.compact, |e| *e.weight() as f64).unwrap();
+    assert_eq!(stable.mean, compact.mean);
+    assert_eq!(stable.mean, -2.0 / 3.0);
+}
+
+// Cycle means of the `i64` edge weights: the minimum with `Min`, the maximum with `Max`.
+enum MeanAPI {
+    Min,
+    Max,
+}
+
+impl<E: EdgeRef<Weight = i64>> ParametricAPI<E, Ratio<i64>> for MeanAPI {
+    fn distance(&self, ratio: &Ratio<i64>, edge: &E) -> Ratio<i64> {
+        let weight = Ratio::from_integer(*edge.weight());
+        match self {
+            MeanAPI::Min => weight - *ratio,
+            MeanAPI::Max => *ratio - weight,
+        }
+    }
+
+    fn zero_cancel(&self, cycle: &[E]) -> Ratio<i64> {
+        let total: i64 = cycle.iter().map(|e| *e.weight()).sum();
+        Ratio::new(total, cycle.len() as i64)
+    }
+}
+
+#[test]
+fn test_stable_graph_parametric() {
+    let h = Holey::new(&random_edges(30, 1..20, false));
+    let min_mean = min_mean_cycle(&h.compact, |e| Ratio::from_integer(*e.weight()))
+        .unwrap()
+        .mean;
+    let max_mean = -min_mean_cycle(&h.compact, |e| Ratio::from_integer(-*e.weight()))
+        .unwrap()
+        .mean;
+    assert!(min_mean < max_mean);
+
+    let mut solver = MaxParametricSolver::new(&h.stable, MeanAPI::Min);
+    let mut dist = vec![Ratio::from_integer(0); h.stable.node_bound()];
+    let mut ratio = Ratio::from_integer(100);
+    let cycle = solver.run(&mut dist, &mut ratio);
+    assert_eq!(ratio, min_mean);
+    assert_eq!(MeanAPI::Min.zero_cancel(&cycle), min_mean);
+
+    let mut solver = MinParametricSolver::new(&h.stable, MeanAPI::Max);
+    let mut dist = vec![Ratio::from_integer(0); h.stable.node_bound()];
+    let mut ratio = Ratio::from_integer(0);
+    let cycle = solver.run(&mut dist, &mut ratio);
+    assert_eq!(ratio, max_mean);
+    assert_eq!(MeanAPI::Max.zero_cancel(&cycle), max_mean);
+
+    let cost = |w: &i64| Ratio::from_integer(*w);
+    let time = |w: &i64| Ratio::from_integer(w % 3 + 1);
+    let stable = MinCycleRatioSolver::with_weights(&h.stable, cost, time).run(Ratio::from(100));
+    let compact = MinCycleRatioSolver::with_weights(&h.compact, cost, time).run(Ratio::from(100));
+    assert_eq!(stable.ratio, compact.ratio);
+    assert_eq!(stable.potentials.len(), h.stable.node_bound());
+    for edge in h.stable.edge_references() {
+        let (u, v) = (edge.source().index(), edge.target().index());
+        let reduced = cost(edge.weight()) - stable.ratio * time(edge.weight());
+        assert!(stable.potentials[v] <= stable.potentials[u] + reduced);
+    }
+}
@@ -0,0 +1,93 @@
+//! Random graph fixtures shared by the unit tests and the benchmarks.
+//!
+//! The benchmarks include this file with `#[path]`, so it depends on petgraph only.
+
+use petgraph::graph::{Graph, NodeIndex};
+
+/// A linear congruential generator: reproducible pseudo-random numbers without a
+/// dependency.
+pub(crate) struct Lcg(u64);
+
+impl Lcg {
+    pub(crate) fn new(seed: u64) -> Self {
+        Lcg(seed)
+    }
+
+    /// A number in `0..m`.
+    pub(crate) fn below(&mut self, m: u64) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (self.0 >> 33) % m
+    }
+}
+
+/// `m` edges between random pairs of the nodes `0..n`, with weights drawn by `weight`.
+/// If `dag`, every edge goes from a lower to a higher node, so that negative weights
+/// cannot form a cycle.
+pub(crate) fn random_edges<W>(
+    rng: &mut Lcg,
+    n: usize,
+    m: usize,
+    dag: bool,
+    mut weight: impl FnMut(&mut Lcg) -> W,
+) -> Vec<(usize, usize, W)> {
+    let mut edges = Vec::with_capacity(m);
+    while edges.len() < m {
+        let u = rng.below(n as u64) as usize;
+        let v = rng.below(n as u64) as usize;
+        if dag && u >= v {
+            continue;
+        }
+        edges.push((u, v, weight(rng)));
+    }
+    edges
+}
+
+/// A graph on `n` nodes with the edges of [`random_edges`].
+pub(crate) fn random_graph<W>(
+    rng: &mut Lcg,
+    n: usize,
+    m: usize,
+    dag: bool,
+    weight: impl FnMut(&mut Lcg) -> W,
+) -> Graph<(), W> {
+    let mut g = Graph::new();
+    for _ in 0..n {
+        g.add_node(());
+    }
+    for (u, v, w) in random_edges(rng, n, m, dag, weight) {
+        g.add_edge(NodeIndex::new(u), NodeIndex::new(v), w);
+    }
+    g
+}
+
+/// A `side` x `side` grid with edges in both directions, with lengths drawn by
+/// `length`; node `side * row + col`.
+pub(crate) fn grid_graph<W>(
+    rng: &mut Lcg,
+    side: usize,
+    mut length: impl FnMut(&mut Lcg) -> W,
+) -> Graph<(), W> {
+    let mut g = Graph::new();
+    for _ in 0..side * side {
+        g.add_node(());
+    }
+    for r in 0..side {
+        for c in 0..side {
+            let u = NodeIndex::new(side * r + c);
+            if c + 1 < side {
+                let v = NodeIndex::new(side * r + c + 1);
+                g.add_edge(u, v, length(rng));
+                g.add_edge(v, u, length(rng));
+            }
+            if r + 1 < side {
+                let v = NodeIndex::new(side * (r + 1) + c);
+                g.add_edge(u, v, length(rng));
+                g.add_edge(v, u, length(rng));
+            }
+        }
+    }
+    g
+}
@@ -0,0 +1,193 @@
+//! Edge weight trait for the shortest path algorithms.
+//!
+//! [`bellman_ford`](crate::bellman_ford), [`find_negative_cycle`](crate::find_negative_cycle)
+//! and [`dijkstra`](crate::dijkstra::dijkstra) only need a zero, an ordering and an
+//! addition; reweighting by potentials also subtracts. Unlike petgraph's `FloatMeasure`,
+//! no infinity is required: unreachable nodes are reported as `None`, so integer,
+//! rational and big integer costs give exact results.
+
+use num::bigint::{BigInt, BigUint};
+use num::rational::Ratio;
+use num::traits::{CheckedAdd, CheckedMul, CheckedSub};
+use num::Integer;
+
+/// Edge weight usable by the shortest path algorithms.
+///
+/// # Example
+/// ```rust
+/// use netoptim_rs::weight::Weight;
+///
+/// assert_eq!(<i32 as Weight>::zero(), 0);
+/// assert_eq!(Weight::checked_add(&2_i32, &3), Some(5));
+/// assert_eq!(Weight::checked_add(&i32::MAX, &1), None);
+/// assert_eq!(Weight::checked_sub(&2_u32, &3), None);
+/// ```
+pub trait Weight: Clone + PartialOrd {
+    /// Whether sums and differences are computed exactly. `false` for floating point,
+    /// whose results are rounded.
+    const EXACT: bool = true;
+
+    /// The additive identity.
+    fn zero() -> Self;
+
+    /// Adds two weights, returning `None` if the sum cannot be represented.
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+
+    /// Subtracts `other` from `self`, returning `None` if the difference cannot be
+    /// represented.
+    fn checked_sub(&self, other: &Self) -> Option<Self>;
+}
+
+macro_rules! impl_weight_float {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                const EXACT: bool = false;
+
+                #[inline]
+                fn zero() -> Self {
+                    0.0
+                }
+
+                #[inline]
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    Some(self + other)
+                }
+
+                #[inline]
+                fn checked_sub(&self, other: &Self) -> Option<Self> {
+                    Some(self - other)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_weight_int {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                #[inline]
+                fn zero() -> Self {
+                    0
+                }
+
+                #[inline]
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+
+                #[inline]
+                fn checked_sub(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_sub(*self, *other)
+                }
+            }
+        )*
+    };
+}
+
+impl_weight_float!(f32, f64);
+impl_weight_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl<T> Weight for Ratio<T>
+where
+    T: Clone + Integer + CheckedAdd + CheckedSub + CheckedMul,
+{
+    #[inline]
+    fn zero() -> Self {
+        Ratio::from_integer(T::zero())
+    }
+
+    #[inline]
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        CheckedAdd::checked_add(self, other)
+    }
+
+    #[inline]
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        CheckedSub::checked_sub(self, other)
+    }
+}
+
+impl Weight for BigInt {
+    #[inline]
+    fn zero() -> Self {
+        BigInt::default()
+    }
+
+    #[inline]
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    #[inline]
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Some(self - other)
+    }
+}
+
+impl Weight for BigUint {
+    #[inline]
+    fn zero() -> Self {
+        BigUint::default()
+    }
+
+    #[inline]
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    #[inline]
+    fn checked_sub(&self, other: &Self) -> Option<Self> {
+        CheckedSub::checked_sub(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weight_int_overflow() {
+        assert_eq!(Weight::checked_add(&i64::MAX, &1), None);
+        assert_eq!(Weight::checked_add(&u8::MAX, &0), Some(u8::MAX));
+        assert_eq!(<usize as Weight>::zero(), 0);
+        assert_eq!(Weight::checked_sub(&i64::MIN, &1), None);
+        assert_eq!(Weight::checked_sub(&0_u8, &1), None);
+        assert_eq!(Weight::checked_sub(&5_u8, &2), Some(3));
+    }
+
+    #[test]
+    fn test_weight_ratio() {
+        let a = Ratio::new(1_i64, 3);
+        let b = Ratio::new(1_i64, 6);
+        assert_eq!(Weight::checked_add(&a, &b), Some(Ratio::new(1, 2)));
+        assert_eq!(<Ratio<i64> as Weight>::zero(), Ratio::from_integer(0));
+
+        let big = Ratio::from_integer(i64::MAX);
+        assert_eq!(Weight::checked_add(&big, &big), None);
+        assert_eq!(Weight::checked_sub(&a, &b), Some(Ratio::new(1, 6)));
+    }
+
+    #[test]
+    fn test_weight_bigint() {
+        let a = BigInt::from(i64::MAX);
+        let sum = Weight::checked_add(&a, &a).unwrap();
+        assert_eq!(sum, BigInt::from(i64::MAX) * BigInt::from(2));
+        assert_eq!(Weight::checked_sub(&a, &sum), Some(-a.clone()));
+        assert_eq!(
+            Weight::checked_sub(&BigUint::from(1_u8), &BigUint::from(2_u8)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_weight_float() {
+        assert_eq!(Weight::checked_add(&1.5_f64, &2.0), Some(3.5));
+        assert_eq!(<f32 as Weight>::zero(), 0.0);
+        const {
+            assert!(!<f64 as Weight>::EXACT);
+            assert!(<Ratio<i32> as Weight>::EXACT && <u8 as Weight>::EXACT);
+        }
+    }
+}
@@ -0,0 +1,334 @@
+//! Yen's k-shortest loopless paths.
+//!
+//! The i-th shortest path is found by deviating from one of the paths found so far:
+//! for every "spur" node on the previous path, keep the root path up to it, mask the
+//! root's nodes and the edges that earlier paths with the same root take out of the
+//! spur node, and run [`dijkstra`](crate::dijkstra::dijkstra) from the spur node on
+//! what remains. The cheapest of all deviations so far is the next path.
+//!
+//! Paths are sequences of nodes; between parallel edges the cheapest one is used.
+
+use petgraph::visit::{
+    EdgeRef, IntoEdges, IntoNodeIdentifiers, NodeCount, NodeIndexable, Visitable,
+};
+
+use crate::dijkstra::{dijkstra_by, DijkstraOptions};
+use crate::error::NetOptimError;
+use crate::validate_node;
+use crate::weight::Weight;
+
+/// \[Generic\] Compute the `k` shortest loopless paths from `source` to `target`
+/// using [Yen's algorithm][yen].
+///
+/// Edge weights must be non-negative. Each of the up to `k` paths is returned with
+/// its cost, cheapest first; fewer are returned if the graph has fewer simple paths.
+/// The first path is the one [`dijkstra_path`](crate::dijkstra::dijkstra_path) finds,
+/// up to ties. Each path costs up to |V| Dijkstra runs, so the total is
+/// O(k·|V|·(|E| + |V|) log |V|).
+///
+/// [yen]: https://en.wikipedia.org/wiki/Yen%27s_algorithm
+///
+/// # Arguments
+/// * `g` - The graph to search
+/// * `source` - The source node index
+/// * `target` - The target node index
+/// * `k` - The number of paths to find
+///
+/// # Returns
+/// * `Ok(paths)` - Up to `k` pairs of cost and nodes, from source to target
+/// * `Err` - As for [`dijkstra_path`](crate::dijkstra::dijkstra_path), except that an
+///   unreachable `target` gives no paths rather than [`NetOptimError::NoPath`]
+///
+/// # Example
+/// ```rust
+/// use petgraph::Graph;
+/// use petgraph::prelude::*;
+/// use netoptim_rs::yen::k_shortest_paths;
+///
+/// let g = Graph::<(), u32>::from_edges(&[
+///     (0, 1, 1),
+///     (1, 3, 1),
+///     (0, 2, 3),
+///     (2, 3, 2),
+///     (1, 2, 1),
+/// ]);
+/// let n = NodeIndex::new;
+/// let paths = k_shortest_paths(&g, n(0), n(3), 5).unwrap();
+/// assert_eq!(
+///     paths,
+///     vec![
+///         (2, vec![n(0), n(1), n(3)]),
+///         (4, vec![n(0), n(1), n(2), n(3)]),
+///         (5, vec![n(0), n(2), n(3)]),
+///     ]
+/// );
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn k_shortest_paths<G>(
+    g: G,
+    source: G::NodeId,
+    target: G::NodeId,
+    k: usize,
+) -> Result<Vec<(G::EdgeWeight, Vec<G::NodeId>)>, NetOptimError<G::NodeId>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable + Visitable,
+    G::EdgeWeight: Weight,
+{
+    validate_node(g, source)?;
+    validate_node(g, target)?;
+    let ix = |i| g.to_index(i);
+
+    let mut found: Vec<(G::EdgeWeight, Vec<G::NodeId>)> = Vec::new();
+    if k == 0 {
+        return Ok(found);
+    }
+    match spur_path(g, source, target, &vec![false; g.node_bound()], &[])? {
+        Some(first) => found.push(first),
+        None => return Ok(found),
+    }
+
+    // Deviations found so far that are not in `found` yet.
+    let mut candidates: Vec<(G::EdgeWeight, Vec<G::NodeId>)> = Vec::new();
+    while found.len() < k {
+        let (_, previous) = found.last().expect("at least one path was found");
+        let previous = previous.clone();
+
+        let mut masked = vec![false; g.node_bound()];
+        let mut root_cost = G::EdgeWeight::zero();
+        for i in 0..previous.len() - 1 {
+            let spur = previous[i];
+            let root = &previous[..=i];
+
+            // Edges out of the spur node that earlier paths with this root take.
+            let blocked: Vec<G::NodeId> = found
+                .iter()
+                .filter(|(_, path)| path.len() > i + 1 && path[..=i] == *root)
+                .map(|(_, path)| path[i + 1])
+                .collect();
+
+            if let Some((spur_cost, spur_nodes)) = spur_path(g, spur, target, &masked, &blocked)? {
+                if let Some(cost) = root_cost.checked_add(&spur_cost) {
+                    let mut path = root[..i].to_vec();
+                    path.extend(spur_nodes);
+                    let known = |(_, p): &(G::EdgeWeight, Vec<G::NodeId>)| *p == path;
+                    if !candidates.iter().any(known) && !found.iter().any(known) {
+                        candidates.push((cost, path));
+                    }
+                }
+            }
+
+            // The spur node becomes part of the root for the next deviation.
+            masked[ix(spur)] = true;
+            let Some(step) = edge_cost(g, spur, previous[i + 1]) else {
+                break;
+            };
+            let Some(cost) = root_cost.checked_add(&step) else {
+                break;
+            };
+            root_cost = cost;
+        }
+
+        // The cheapest candidate is the next path.
+        let Some(best) = (0..candidates.len()).reduce(|best, c| {
+            if candidates[c].0 < candidates[best].0 {
+                c
+            } else {
+                best
+            }
+        }) else {
+            break;
+        };
+        found.push(candidates.swap_remove(best));
+    }
+
+    Ok(found)
+}
+
+// Shortest path from `spur` to `target` avoiding the `masked` nodes and the edges from
+// `spur` to any node in `blocked`, with its cost.
+#[allow(clippy::type_complexity)]
+fn spur_path<G>(
+    g: G,
+    spur: G::NodeId,
+    target: G::NodeId,
+    masked: &[bool],
+    blocked: &[G::NodeId],
+) -> Result<Option<(G::EdgeWeight, Vec<G::NodeId>)>, NetOptimError<G::NodeId>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable + Visitable,
+    G::EdgeWeight: Weight,
+{
+    let ix = |i| g.to_index(i);
+    let masked_cost = |edge: G::EdgeRef| {
+        let next = edge.target();
+        if masked[ix(next)] || (edge.source() == spur && blocked.contains(&next)) {
+            Ok(None)
+        } else {
+            Ok(Some(edge.weight().clone()))
+        }
+    };
+    let options = DijkstraOptions::new().with_targets([target]);
+    let result = dijkstra_by(g, spur, masked_cost, &options)?;
+
+    let Some(cost) = result.distances[ix(target)].clone() else {
+        return Ok(None);
+    };
+    let mut path = vec![target];
+    let mut current = target;
+    while current != spur {
+        current = result.predecessors[ix(current)].expect("reached nodes have a predecessor");
+        path.push(current);
+    }
+    path.reverse();
+    Ok(Some((cost, path)))
+}
+
+// Cost of the cheapest edge from `u` to `v`.
+fn edge_cost<G>(g: G, u: G::NodeId, v: G::NodeId) -> Option<G::EdgeWeight>
+where
+    G: IntoEdges,
+    G::EdgeWeight: Weight,
+{
+    g.edges(u)
+        .filter(|edge| edge.target() == v)
+        .map(|edge| edge.weight().clone())
+        .reduce(|a, b| if b < a { b } else { a })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dijkstra::dijkstra_path;
+    use petgraph::prelude::*;
+    use petgraph::Graph;
+
+    #[test]
+    fn test_k_shortest_paths_classic_example() {
+        // The example from the Wikipedia article: C=0, D=1, E=2, F=3, G=4, H=5.
+        let g = Graph::<(), u32>::from_edges([
+            (0, 1, 3),
+            (0, 2, 2),
+            (1, 3, 4),
+            (2, 1, 1),
+            (2, 3, 2),
+            (2, 4, 3),
+            (3, 4, 2),
+            (3, 5, 1),
+            (4, 5, 2),
+        ]);
+        let n = NodeIndex::new;
+        let paths = k_shortest_paths(&g, n(0), n(5), 3).unwrap();
+        assert_eq!(
+            paths,
+            vec![
+                (5, vec![n(0), n(2), n(3), n(5)]),
+                (7, vec![n(0), n(2), n(4), n(5)]),
+                (8, vec![n(0), n(1), n(3), n(5)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_k_shortest_paths_all_simple_paths() {
+        // A complete DAG on 5 nodes has 2^3 = 8 simple paths from 0 to 4.
+        let mut edges = Vec::new();
+        for u in 0..5u32 {
+            for v in u + 1..5 {
+                edges.push((u, v, 1 + u + v));
+            }
+        }
+        let g = Graph::<(), u32>::from_edges(edges);
+        let paths = k_shortest_paths(&g, NodeIndex::new(0), NodeIndex::new(4), 20).unwrap();
+        assert_eq!(paths.len(), 8);
+        for window in paths.windows(2) {
+            assert!(window[0].0 <= window[1].0);
+        }
+        for (k, (_, path)) in paths.iter().enumerate() {
+            assert!(paths[k + 1..].iter().all(|(_, other)| other != path));
+        }
+    }
+
+    #[test]
+    fn test_k_shortest_paths_are_loopless_with_cycles() {
+        let g = Graph::<(), f64>::from_edges([
+            (0, 1, 1.0),
+            (1, 0, 1.0),
+            (1, 2, 1.0),
+            (2, 1, 1.0),
+            (2, 3, 1.0),
+            (0, 2, 3.0),
+            (1, 3, 4.0),
+        ]);
+        let paths = k_shortest_paths(&g, NodeIndex::new(0), NodeIndex::new(3), 10).unwrap();
+        // 0-1-2-3, 0-2-3, 0-1-3 and 0-2-1-3.
+        let costs: Vec<f64> = paths.iter().map(|(cost, _)| *cost).collect();
+        assert_eq!(costs, vec![3.0, 4.0, 5.0, 8.0]);
+        for (_, path) in &paths {
+            let mut nodes = path.clone();
+            nodes.sort();
+            nodes.dedup();
+            assert_eq!(nodes.len(), path.len());
+        }
+    }
+
+    #[test]
+    fn test_k_shortest_paths_first_matches_dijkstra_path() {
+        let g =
+            Graph::<(), u64>::from_edges([(0, 1, 7), (0, 2, 9), (1, 2, 1), (2, 3, 2), (1, 3, 6)]);
+        let source = NodeIndex::new(0);
+        let target = NodeIndex::new(3);
+        let paths = k_shortest_paths(&g, source, target, 1).unwrap();
+        assert_eq!(paths.len(), 1);
+        assert_eq!(Ok(paths[0].1.clone()), dijkstra_path(&g, source, target));
+        assert_eq!(paths[0].0, 10);
+    }
+
+    #[test]
+    fn test_k_shortest_paths_parallel_edges() {
+        let g = Graph::<(), u32>::from_edges([(0, 1, 5), (0, 1, 2), (1, 2, 1), (0, 2, 4)]);
+        let n = NodeIndex::new;
+        let paths = k_shortest_paths(&g, n(0), n(2), 5).unwrap();
+        assert_eq!(
+            paths,
+            vec![(3, vec![n(0), n(1), n(2)]), (4, vec![n(0), n(2)])]
+        );
+    }
+
+    #[test]
+    fn test_k_shortest_paths_no_path() {
+        let g = Graph::<(), u32>::from_edges([(0, 1, 1), (2, 1, 1)]);
+        let paths = k_shortest_paths(&g, NodeIndex::new(0), NodeIndex::new(2), 3).unwrap();
+        assert!(paths.is_empty());
+    }
+
+    #[test]
+    fn test_k_shortest_paths_source_is_target() {
+        let g = Graph::<(), u32>::from_edges([(0, 1, 1), (1, 0, 1)]);
+        let a = NodeIndex::new(0);
+        assert_eq!(k_shortest_paths(&g, a, a, 3).unwrap(), vec![(0, vec![a])]);
+        assert!(k_shortest_paths(&g, a, a, 0).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_k_shortest_paths_negative_weight() {
+        let g = Graph::<(), i32>::from_edges([(0, 1, -1), (1, 2, 1)]);
+        let n = NodeIndex::new;
+        assert_eq!(
+            k_shortest_paths(&g, n(0), n(2), 2).unwrap_err(),
+            NetOptimError::NegativeWeight {
+                source: n(0),
+                target: n(1)
+            }
+        );
+    }
+
+    #[test]
+    fn test_k_shortest_paths_invalid_target() {
+        let g = Graph::<(), u32>::from_edges([(0, 1, 1)]);
+        let n = NodeIndex::new;
+        assert_eq!(
+            k_shortest_paths(&g, n(0), n(3), 2).unwrap_err(),
+            NetOptimError::InvalidNode(n(3))
+        );
+    }
+}